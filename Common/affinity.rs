@@ -0,0 +1,201 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    Thread pinning: the CPU every worker thread of a backend is bound to,  //
+//    following a policy in the spirit of OMP_PROC_BIND/OMP_PLACES, and what //
+//    was actually applied (through sched_setaffinity, on Linux).            //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+use std::fs;
+use std::sync::Mutex;
+
+use crate::args;
+use crate::host;
+
+
+
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub enum Binding
+{
+    #[default]
+    None,           // left to the scheduler
+    Compact,        // consecutive threads on neighbouring CPUs
+    Spread,         // threads spread evenly over the CPUs (and so, sockets)
+    List(Vec<usize>), // thread i on the i-th CPU of the list (cyclically)
+}
+
+impl Binding
+{
+    // parses "none", "compact", "spread" or "list:0,2,4"
+    pub fn from_name( name: &str ) -> Result<Binding, String>
+    {
+        return match name
+        {
+            "none" => Ok(Binding::None),
+            "compact" => Ok(Binding::Compact),
+            "spread" => Ok(Binding::Spread),
+            _ => match name.strip_prefix("list:")
+            {
+                Some(list) =>
+                {
+                    let cpus: Result<Vec<usize>, _> =
+                            list.split(',').map(|cpu| cpu.trim().parse::<usize>()).collect();
+                    match cpus
+                    {
+                        Ok(cpus) if !cpus.is_empty() => Ok(Binding::List(cpus)),
+                        _ => Err(format!("invalid CPU list in --bind: \"{}\"", list)),
+                    }
+                }
+                None => Err(format!("invalid --bind policy \"{}\" \
+                                     (none, compact, spread or list:CPU,CPU,...)", name)),
+            },
+        };
+    }
+
+    // the binding of "--bind POLICY" in 'args' (none if absent)
+    pub fn from_args( args: &[String] ) -> Result<Binding, String>
+    {
+        return match args::option_value(args, "--bind")?
+        {
+            Some(name) => Binding::from_name(name),
+            None => Ok(Binding::None),
+        };
+    }
+
+    pub fn name( &self ) -> &'static str
+    {
+        return match self
+        {
+            Binding::None => "none",
+            Binding::Compact => "compact",
+            Binding::Spread => "spread",
+            Binding::List(_) => "list",
+        };
+    }
+
+    // the CPU each of 'threads' threads goes to (None for all, if unbound)
+    pub fn plan( &self, threads: &usize ) -> Vec<Option<usize>>
+    {
+        let cpus: Vec<usize> = match self
+        {
+            Binding::None => return vec![None; *threads],
+            Binding::List(cpus) => cpus.clone(),
+            _ => topology_order(&host::allowed_cpus()),
+        };
+        if cpus.is_empty() { return vec![None; *threads]; }
+
+        return (0usize..*threads).map(|thread| Some(match self
+        {
+            Binding::Spread => cpus[((thread * cpus.len()) / threads) % cpus.len()],
+            _ => cpus[thread % cpus.len()],
+        })).collect();
+    }
+}
+
+// 'cpus' sorted by socket, then core, so neighbours share caches
+fn topology_order( cpus: &[usize] ) -> Vec<usize>
+{
+    let id = |cpu: &usize, file: &str| -> i64
+    {
+        return fs::read_to_string(format!("/sys/devices/system/cpu/cpu{}/topology/{}", cpu, file))
+                .ok().and_then(|id| id.trim().parse::<i64>().ok()).unwrap_or(0);
+    };
+    let mut sorted: Vec<usize> = cpus.to_vec();
+    sorted.sort_by_key(|cpu| (id(cpu, "physical_package_id"), id(cpu, "core_id"), *cpu));
+    return sorted;
+}
+
+// binds the calling thread to 'cpu', returning whether it worked
+#[cfg(target_os = "linux")]
+pub fn bind_current_thread( cpu: &usize ) -> bool
+{
+    if *cpu >= (libc::CPU_SETSIZE as usize) { return false; }
+    // SAFETY: 'set' is a properly sized, initialized cpu_set_t
+    unsafe
+    {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_SET(*cpu, &mut set);
+        return libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) == 0;
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn bind_current_thread( _cpu: &usize ) -> bool
+{
+    return false;
+}
+
+
+
+/* the binding of the threads of a backend: what was planned for each of
+ * them, and what was actually applied
+ */
+pub struct Placement
+{
+    binding: Binding,
+    plan:    Vec<Option<usize>>,
+    applied: Mutex<Vec<Option<usize>>>,
+}
+
+impl Placement
+{
+    pub fn new( binding: &Binding, threads: &usize ) -> Placement
+    {
+        return Placement
+        {
+            binding: binding.clone(),
+            plan:    binding.plan(threads),
+            applied: Mutex::new(vec![None; *threads]),
+        };
+    }
+
+    // binds the calling thread, the 'thread'-th of the backend, as planned
+    pub fn apply( &self, thread: &usize )
+    {
+        if let Some(Some(cpu)) = self.plan.get(*thread)
+        {
+            let bound: Option<usize> = if bind_current_thread(cpu) { Some(*cpu) } else { None };
+            self.applied.lock().unwrap()[*thread] = bound;
+        }
+    }
+
+    /* the binding actually applied, as "compact: 0,1,2,3" (the CPU of every
+     * thread, in order, '-' for those that could not be bound)
+     */
+    pub fn describe( &self ) -> String
+    {
+        if self.binding == Binding::None { return String::from("none"); }
+        let cpus: Vec<String> = self.applied.lock().unwrap().iter()
+                .map(|cpu| cpu.map_or(String::from("-"), |cpu| cpu.to_string()))
+                .collect();
+        return format!("{}: {}", self.binding.name(), cpus.join(","));
+    }
+}
@@ -0,0 +1,90 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    Helpers for the command line options shared by the binaries.           //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+use crate::verification::EXIT_INVALID;
+
+
+
+/* the value of option 'name' in 'args', given either as "name VALUE" or as
+ * "name=VALUE"; None if the option is absent, and an error if it is the last
+ * argument and has no value
+ */
+pub fn option_value<'a>( args: &'a [String], name: &str ) -> Result<Option<&'a str>, String>
+{
+    for (i, arg) in args.iter().enumerate()
+    {
+        if let Some(value) = arg.strip_prefix(name).and_then(|rest| rest.strip_prefix('='))
+        {
+            return Ok(Some(value));
+        }
+        if arg == name
+        {
+            return match args.get(i + 1)
+            {
+                Some(value) => Ok(Some(value.as_str())),
+                None => Err(format!("missing value after {}", name)),
+            };
+        }
+    }
+    return Ok(None);
+}
+
+/* the value of 'result', or its error printed and the process ended (as one
+ * of invalid arguments or configuration)
+ */
+pub fn or_exit<T>( result: Result<T, String> ) -> T
+{
+    return match result
+    {
+        Ok(value) => value,
+        Err(message) => { eprintln!(" {}", message); std::process::exit(EXIT_INVALID); }
+    };
+}
+
+/* the whole number given to option 'name' in 'args', or 'default' if the
+ * option is absent; an error if it is not a number of at least 'minimum'
+ */
+pub fn number_option( args: &[String], name: &str, default: &usize, minimum: &usize )
+        -> Result<usize, String>
+{
+    return match option_value(args, name)?
+    {
+        None => Ok(*default),
+        Some(value) => match value.trim().parse::<usize>()
+        {
+            Ok(n) if n >= *minimum => Ok(n),
+            _ => Err(format!("invalid value for {}: \"{}\"", name, value)),
+        },
+    };
+}
@@ -0,0 +1,393 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    The backends through which a kernel runs its parallel parts, so it is  //
+//    written once and the Serial, Rayon and TC binaries only differ in the  //
+//    backend they instantiate.                                              //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+use std::ops::Range;
+#[cfg(feature = "rayon")]
+use std::sync::Arc;
+use std::sync::mpsc;
+use std::thread;
+
+use crate::affinity::{Binding, Placement};
+
+
+
+// the number of threads the system offers (1 if it cannot be queried)
+pub fn available_threads() -> usize
+{
+    return thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+}
+
+/* the number of threads asked for, through "--threads N" in 'args' or else
+ * through the NPB_NUM_THREADS environment variable (the counterpart of
+ * OMP_NUM_THREADS); None if neither is given
+ */
+pub fn requested_threads( args: &[String] ) -> Result<Option<usize>, String>
+{
+    let parse = |value: &str, source: &str| -> Result<Option<usize>, String>
+    {
+        return match value.trim().parse::<usize>()
+        {
+            Ok(n) if n > 0 => Ok(Some(n)),
+            _ => Err(format!("invalid number of threads in {}: \"{}\"", source, value)),
+        };
+    };
+
+    if let Some(value) = crate::args::option_value(args, "--threads")?
+    {
+        return parse(value, "--threads");
+    }
+
+    return match std::env::var("NPB_NUM_THREADS")
+    {
+        Ok(value) => parse(&value, "NPB_NUM_THREADS"),
+        Err(_) => Ok(None),
+    };
+}
+
+
+
+/* splits 0..n into 'parts' contiguous ranges, whose lengths differ by one at
+ * most, and returns the 'part'-th of them
+ */
+pub fn split_range( n: &usize, parts: &usize, part: &usize ) -> Range<usize>
+{
+    let (base, extra): (usize, usize) = (n / parts, n % parts);
+    let start: usize = (part * base) + usize::min(*part, extra);
+    let end: usize = start + base + (if *part < extra { 1 } else { 0 });
+    return start..end;
+}
+
+
+
+pub trait ParallelBackend: Sync
+{
+    // a short name for the reports ("serial", "Rayon" or "TC")
+    fn name( &self ) -> &'static str;
+
+    fn num_threads( &self ) -> usize;
+
+    // the thread binding applied (see affinity::Placement::describe)
+    fn binding( &self ) -> String
+    {
+        return String::from("none");
+    }
+
+    /* calls 'f' with the index and the contents of every 'chunk_size' long
+     * chunk of 'data' (the last one may be shorter), possibly in parallel
+     */
+    fn parallel_for_chunks<T, F>( &self, data: &mut [T], chunk_size: &usize, f: F )
+        where T: Send, F: Fn(usize, &mut [T]) + Sync;
+
+    /* calls 'map' on disjoint ranges covering 0..n, possibly in parallel, and
+     * combines their results with 'reduce' (in the order of the ranges)
+     */
+    fn map_reduce<R, M, D>( &self, n: &usize, map: M, reduce: D ) -> R
+        where R: Send, M: Fn(Range<usize>) -> R + Sync, D: Fn(R, R) -> R + Sync;
+}
+
+
+
+// everything runs in the calling thread
+pub struct Serial;
+
+impl ParallelBackend for Serial
+{
+    fn name( &self ) -> &'static str
+    {
+        return "serial";
+    }
+
+    fn num_threads( &self ) -> usize
+    {
+        return 1;
+    }
+
+    fn parallel_for_chunks<T, F>( &self, data: &mut [T], chunk_size: &usize, f: F )
+        where T: Send, F: Fn(usize, &mut [T]) + Sync
+    {
+        for (i, chunk) in data.chunks_mut(*chunk_size).enumerate() { f(i, chunk); }
+    }
+
+    fn map_reduce<R, M, D>( &self, n: &usize, map: M, _reduce: D ) -> R
+        where R: Send, M: Fn(Range<usize>) -> R + Sync, D: Fn(R, R) -> R + Sync
+    {
+        return map(0..*n);
+    }
+}
+
+
+
+/* standard threads, one per range (or group of chunks), sending their results
+ * back through a channel
+ */
+pub struct ThreadsChannels
+{
+    threads:   usize,
+    placement: Placement,
+}
+
+impl ThreadsChannels
+{
+    pub fn new( threads: &usize ) -> ThreadsChannels
+    {
+        return ThreadsChannels::with_binding(threads, &Binding::None);
+    }
+
+    // every thread spawned is bound as 'binding' says
+    pub fn with_binding( threads: &usize, binding: &Binding ) -> ThreadsChannels
+    {
+        let threads: usize = usize::max(*threads, 1);
+        return ThreadsChannels { threads, placement: Placement::new(binding, &threads) };
+    }
+}
+
+impl ParallelBackend for ThreadsChannels
+{
+    fn name( &self ) -> &'static str
+    {
+        return "TC";
+    }
+
+    fn num_threads( &self ) -> usize
+    {
+        return self.threads;
+    }
+
+    fn binding( &self ) -> String
+    {
+        return self.placement.describe();
+    }
+
+    fn parallel_for_chunks<T, F>( &self, data: &mut [T], chunk_size: &usize, f: F )
+        where T: Send, F: Fn(usize, &mut [T]) + Sync
+    {
+        let mut chunks: Vec<(usize, &mut [T])> = data.chunks_mut(*chunk_size).enumerate().collect();
+        let parts: usize = usize::max(usize::min(self.threads, chunks.len()), 1);
+        let mut groups: Vec<Vec<(usize, &mut [T])>> = Vec::with_capacity(parts);
+        for part in (0usize..parts).rev()
+        {
+            let range: Range<usize> = split_range(&chunks.len(), &parts, &part);
+            groups.push(chunks.split_off(range.start));
+        }
+
+        thread::scope(|scope|
+        {
+            for (part, group) in groups.into_iter().rev().enumerate()
+            {
+                let (f, placement) = (&f, &self.placement);
+                scope.spawn(move ||
+                {
+                    placement.apply(&part);
+                    for (i, chunk) in group { f(i, chunk); }
+                });
+            }
+        });
+    }
+
+    fn map_reduce<R, M, D>( &self, n: &usize, map: M, reduce: D ) -> R
+        where R: Send, M: Fn(Range<usize>) -> R + Sync, D: Fn(R, R) -> R + Sync
+    {
+        let parts: usize = usize::max(usize::min(self.threads, *n), 1);
+        let (result_sender, result_receiver) = mpsc::channel::<(usize, R)>();
+
+        thread::scope(|scope|
+        {
+            for part in 0usize..parts
+            {
+                let (local_result_sender, map, placement) =
+                        (result_sender.clone(), &map, &self.placement);
+                scope.spawn(move ||
+                {
+                    placement.apply(&part);
+                    local_result_sender.send((part, map(split_range(n, &parts, &part)))).unwrap();
+                });
+            }
+        });
+        drop(result_sender);
+
+        let mut results: Vec<(usize, R)> = result_receiver.iter().collect();
+        results.sort_by_key(|(part, _)| *part);
+        return results.into_iter().map(|(_, result)| result).reduce(reduce).unwrap();
+    }
+}
+
+
+
+// a rayon thread pool of its own
+#[cfg(feature = "rayon")]
+pub struct Rayon
+{
+    pool:      rayon::ThreadPool,
+    placement: Arc<Placement>,
+}
+
+#[cfg(feature = "rayon")]
+impl Rayon
+{
+    /* 'threads' threads, or rayon's default (RAYON_NUM_THREADS or the number
+     * of available threads) if None; falls back to a single thread if the
+     * pool cannot be built
+     */
+    pub fn new( threads: &Option<usize> ) -> Rayon
+    {
+        return Rayon::with_binding(threads, &Binding::None);
+    }
+
+    // every thread of the pool is bound (as it starts) as 'binding' says
+    pub fn with_binding( threads: &Option<usize>, binding: &Binding ) -> Rayon
+    {
+        let threads: usize = threads.unwrap_or_else(rayon::current_num_threads);
+        let placement: Arc<Placement> = Arc::new(Placement::new(binding, &threads));
+        let handler_placement: Arc<Placement> = Arc::clone(&placement);
+        let builder = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .start_handler(move |index| handler_placement.apply(&index));
+        let pool: rayon::ThreadPool = match builder.build()
+        {
+            Ok(pool) => pool,
+            Err(_) => rayon::ThreadPoolBuilder::new().num_threads(1).build().unwrap(),
+        };
+        return Rayon { pool, placement };
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl ParallelBackend for Rayon
+{
+    fn name( &self ) -> &'static str
+    {
+        return "Rayon";
+    }
+
+    fn num_threads( &self ) -> usize
+    {
+        return self.pool.current_num_threads();
+    }
+
+    fn binding( &self ) -> String
+    {
+        return self.placement.describe();
+    }
+
+    fn parallel_for_chunks<T, F>( &self, data: &mut [T], chunk_size: &usize, f: F )
+        where T: Send, F: Fn(usize, &mut [T]) + Sync
+    {
+        use rayon::prelude::*;
+        self.pool.install(|| data.par_chunks_mut(*chunk_size).enumerate().for_each(|(i, chunk)| f(i, chunk)));
+    }
+
+    fn map_reduce<R, M, D>( &self, n: &usize, map: M, reduce: D ) -> R
+        where R: Send, M: Fn(Range<usize>) -> R + Sync, D: Fn(R, R) -> R + Sync
+    {
+        use rayon::prelude::*;
+        let parts: usize = usize::max(usize::min(self.num_threads(), *n), 1);
+        return self.pool.install(||
+        {
+            (0usize..parts).into_par_iter()
+                           .map(|part| map(split_range(n, &parts, &part)))
+                           .reduce_with(&reduce).unwrap()
+        });
+    }
+}
+
+
+
+// a backend chosen at run time (by a library caller, or the npb driver)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BackendKind
+{
+    Serial,
+    Rayon,
+    ThreadsChannels,
+}
+
+impl BackendKind
+{
+    // every kind this build can run
+    pub fn available() -> Vec<BackendKind>
+    {
+        let mut kinds: Vec<BackendKind> = vec![BackendKind::Serial];
+        if cfg!(feature = "rayon") { kinds.push(BackendKind::Rayon); }
+        kinds.push(BackendKind::ThreadsChannels);
+        return kinds;
+    }
+
+    // parses "serial", "rayon" or "tc" (in any case)
+    pub fn from_name( name: &str ) -> Result<BackendKind, String>
+    {
+        let kind: BackendKind =
+                match name.to_lowercase().as_str()
+                {
+                    "serial" => BackendKind::Serial,
+                    "rayon" => BackendKind::Rayon,
+                    "tc" => BackendKind::ThreadsChannels,
+                    _ => return Err(format!("unknown backend \"{}\" (available: {})", name,
+                                            BackendKind::available().iter()
+                                                    .map(|k| k.name().to_lowercase())
+                                                    .collect::<Vec<String>>().join(", "))),
+                };
+        if !BackendKind::available().contains(&kind)
+        {
+            return Err(format!("backend \"{}\" needs the \"rayon\" feature", name));
+        }
+        return Ok(kind);
+    }
+
+    // the same names as the backends' own
+    pub fn name( &self ) -> &'static str
+    {
+        return match self
+        {
+            BackendKind::Serial => "serial",
+            BackendKind::Rayon => "Rayon",
+            BackendKind::ThreadsChannels => "TC",
+        };
+    }
+
+    // the number of threads a backend of this kind gets when 'requested' are
+    pub fn threads( &self, requested: &Option<usize> ) -> usize
+    {
+        return match self
+        {
+            BackendKind::Serial => 1,
+            #[cfg(feature = "rayon")]
+            BackendKind::Rayon => requested.unwrap_or_else(rayon::current_num_threads),
+            #[cfg(not(feature = "rayon"))]
+            BackendKind::Rayon => requested.unwrap_or(1),
+            BackendKind::ThreadsChannels => requested.unwrap_or_else(available_threads),
+        };
+    }
+}
@@ -0,0 +1,135 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    Comparison of a run with a baseline (a JSON result saved by a previous //
+//    run with "--json"), flagging the metrics that regressed by more than a //
+//    threshold.                                                             //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+use std::fs;
+
+use crate::args;
+use crate::json::{self, Value};
+
+
+
+/* the compared metrics: name, place in the JSON document, and whether the
+ * higher the better
+ */
+const METRICS: [(&str, &str, &str, bool); 4] =
+[
+    ("Time median (s)", "time", "median", false),
+    ("Time mean (s)",   "time", "mean",   false),
+    ("Mop/s median",    "mops", "median", true),
+    ("Mop/s mean",      "mops", "mean",   true),
+];
+
+// the fields two runs must agree on to be compared
+const IDENTITY: [&str; 5] = ["benchmark", "class", "backend", "threads", "verification"];
+
+
+
+// the regression threshold of "--threshold P" in 'args', in percent (5 if absent)
+pub fn threshold( args: &[String] ) -> Result<f64, String>
+{
+    return match args::option_value(args, "--threshold")?
+    {
+        None => Ok(5.0),
+        Some(value) => match value.trim().trim_end_matches('%').parse::<f64>()
+        {
+            Ok(p) if p >= 0.0 => Ok(p),
+            _ => Err(format!("invalid value for --threshold: \"{}\"", value)),
+        },
+    };
+}
+
+fn metric( document: &Value, group: &str, statistic: &str ) -> Option<f64>
+{
+    return document.get(group).and_then(|g| g.get(statistic)).and_then(|v| v.as_f64());
+}
+
+/* compares the run described by 'current' (a document as written by
+ * "--json") with the one saved in the file 'path', printing the deltas of
+ * every metric; returns whether any of them regressed by more than
+ * 'threshold' percent, and an error if the runs cannot be compared (or
+ * either lacks a finite metric, as a run written with "null" ones does)
+ */
+pub fn compare( current: &str, path: &str, threshold: &f64 ) -> Result<bool, String>
+{
+    let text: String = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let baseline: Value = json::parse(&text).map_err(|e| format!("{}: {}", path, e))?;
+    let current: Value = json::parse(current)?;
+
+    for field in IDENTITY
+    {
+        if baseline.get(field) != current.get(field)
+        {
+            return Err(format!("cannot compare with {}: \"{}\" differs ({} there, {} here)",
+                               path, field, show(baseline.get(field)), show(current.get(field))));
+        }
+    }
+
+	println!("\n Baseline comparison ({}, threshold {}%):\n", path, threshold);
+	println!(" {:<16} {:>14} {:>14} {:>9}", "Metric", "Baseline", "Current", "Delta");
+	let mut regressed: bool = false;
+	for (label, group, statistic, higher_is_better) in METRICS
+	{
+	    let (before, after): (f64, f64) =
+	            match (metric(&baseline, group, statistic), metric(&current, group, statistic))
+	            {
+	                (Some(before), Some(after)) if before > 0.0 => (before, after),
+	                (Some(_), Some(_)) =>
+	                    return Err(format!("{}: {}.{} is not positive", path, group, statistic)),
+	                (None, _) => return Err(format!("{}: no finite {}.{}", path, group, statistic)),
+	                (_, None) => return Err(format!("no finite {}.{} in this run", group, statistic)),
+	            };
+	    let delta: f64 = ((after - before) / before) * 100.0;
+	    let worse: bool = if higher_is_better { -delta > *threshold } else { delta > *threshold };
+	    regressed |= worse;
+	    println!(" {:<16} {:>14.4} {:>14.4} {:>+8.2}%{}", label, before, after, delta,
+	            if worse { "  REGRESSION" } else { "" });
+	}
+	println!();
+
+    return Ok(regressed);
+}
+
+fn show( value: Option<&Value> ) -> String
+{
+    return match value
+    {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Number(n)) => n.to_string(),
+        Some(Value::Bool(b)) => b.to_string(),
+        Some(_) => String::from("?"),
+        None => String::from("nothing"),
+    };
+}
@@ -0,0 +1,114 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    Criterion micro-benchmarks of the random number generator and of the   //
+//    inner loops of EP (run with "cargo bench"), to compare compiler flags  //
+//    and generator implementations without running a whole class.           //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+#![allow(clippy::needless_return)]
+
+
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use rusty_npb::ep::{self, EpSums, A, NK, NK_PLUS, S};
+use rusty_npb::f64rand;
+
+
+
+fn randlc( c: &mut Criterion )
+{
+    let mut x: f64 = S;
+    c.bench_function("randlc", |b| b.iter(|| f64rand::randlc(black_box(&mut x), black_box(&A))));
+}
+
+// from a few numbers to a whole batch (2NK)
+fn nrandlc( c: &mut Criterion )
+{
+    let mut group = c.benchmark_group("nrandlc");
+    for n in [64usize, 1024, 16384, 2 * NK]
+    {
+        let mut y: Vec<f64> = vec![0.0; n];
+        let mut x: f64 = S;
+        group.throughput(Throughput::Elements(n as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(n), &n,
+                               |b, n| b.iter(|| f64rand::nrandlc(n, black_box(&mut x), &A, &mut y)));
+    }
+    group.finish();
+}
+
+// the seeds of the first batch, and of the last ones of classes S, B and E
+fn jump_ahead( c: &mut Criterion )
+{
+    let an: f64 = ep::batch_multiplier();
+    let mut group = c.benchmark_group("jump-ahead");
+    for k in [0usize, (1 << 8) - 1, (1 << 14) - 1, (1 << 24) - 1]
+    {
+        group.bench_with_input(BenchmarkId::from_parameter(k), &k,
+                               |b, k| b.iter(|| ep::batch_seed(black_box(k), &an)));
+    }
+    group.finish();
+}
+
+// the acceptance-rejection loop over the NK pairs of the first batch
+fn gaussian_pairs( c: &mut Criterion )
+{
+    let mut x: Vec<f64> = vec![0.0; NK_PLUS];
+    let mut seed: f64 = ep::batch_seed(&0, &ep::batch_multiplier());
+    let mut sums: EpSums = EpSums::default();
+    f64rand::nrandlc(&(2 * NK), &mut seed, &A, &mut x);
+
+    let mut group = c.benchmark_group("gaussian pairs");
+    group.throughput(Throughput::Elements(NK as u64));
+    group.bench_function("batch", |b| b.iter(|| ep::tally_gaussian_pairs(black_box(&x), &mut sums)));
+    group.finish();
+}
+
+// a whole batch: its seed, 2NK uniform numbers and NK gaussian pairs
+fn batch( c: &mut Criterion )
+{
+    let an: f64 = ep::batch_multiplier();
+    let mut x: Vec<f64> = vec![0.0; NK_PLUS];
+    let mut sums: EpSums = EpSums::default();
+
+    let mut group = c.benchmark_group("EP batch");
+    group.throughput(Throughput::Elements((2 * NK) as u64));
+    group.bench_function("batch", |b| b.iter(|| ep::run_batch(black_box(&1), &an, &mut x, &mut sums, &mut || ())));
+    group.finish();
+}
+
+
+
+criterion_group!(benches, randlc, nrandlc, jump_ahead, gaussian_pairs, batch);
+criterion_main!(benches);
@@ -0,0 +1,167 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    Records how a benchmark package was built (the rustc that built it,    //
+//    the target, the cargo profile, the effective RUSTFLAGS and the locked  //
+//    versions of its dependencies) as environment variables of its          //
+//    compilation, for print_results to report. The build scripts of         //
+//    Serial, Rayon and TC only call run(), this module being one of their   //
+//    build dependencies.                                                    //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+
+
+// the value of the build environment variable 'name', or "" if unset
+fn var( name: &str ) -> String
+{
+    return env::var(name).unwrap_or_default();
+}
+
+// the "-C target-cpu" given in 'flags', if any
+fn target_cpu( flags: &[String] ) -> Option<String>
+{
+    let mut codegen: bool = false;
+    for flag in flags
+    {
+        let option: &str =
+                if let Some(rest) = flag.strip_prefix("-C") { rest }
+                else if codegen { flag.as_str() }
+                else { "" };
+        codegen = flag == "-C";
+        if let Some(cpu) = option.strip_prefix("target-cpu=") { return Some(cpu.to_string()); }
+    }
+    return None;
+}
+
+/* the "name version" of the dependencies of the packages without a source
+ * (this one, and the path dependencies) in Cargo.lock
+ */
+fn locked_dependencies( lock: &str ) -> Vec<String>
+{
+    // name, version, source and dependencies of every locked package
+    let mut packages: Vec<(String, String, bool, Vec<String>)> = Vec::new();
+    let mut in_dependencies: bool = false;
+    for line in lock.lines().map(|line| line.trim())
+    {
+        if line == "[[package]]" { packages.push((String::new(), String::new(), false, Vec::new())); }
+        let package = match packages.last_mut() { Some(package) => package, None => continue };
+        if in_dependencies
+        {
+            if line == "]" { in_dependencies = false; }
+            else
+            {
+                let dependency: &str = line.trim_end_matches(',').trim_matches('"');
+                package.3.push(dependency.split(' ').next().unwrap_or("").to_string());
+            }
+        }
+        else if let Some(name) = line.strip_prefix("name = ") { package.0 = name.trim_matches('"').to_string(); }
+        else if let Some(version) = line.strip_prefix("version = ") { package.1 = version.trim_matches('"').to_string(); }
+        else if line.starts_with("source = ") { package.2 = true; }
+        else if line == "dependencies = [" { in_dependencies = true; }
+    }
+
+    let mut dependencies: Vec<String> = Vec::new();
+    for (_name, _version, _sourced, names) in packages.iter().filter(|package| !package.2)
+    {
+        for name in names
+        {
+            if let Some(dependency) = packages.iter().find(|package| (&package.0 == name) && package.2)
+            {
+                let entry: String = format!("{} {}", dependency.0, dependency.1);
+                if !dependencies.contains(&entry) { dependencies.push(entry); }
+            }
+        }
+    }
+    dependencies.sort();
+    return dependencies;
+}
+
+
+/* the cargo profile of the build, which PROFILE only gives as "debug" or
+ * "release": the name of the directory of the profile in the target
+ * directory, the fourth ancestor of OUT_DIR ("dev" building into "debug")
+ */
+fn profile() -> String
+{
+    let out_dir: String = var("OUT_DIR");
+    let directory: &str = Path::new(&out_dir).ancestors().nth(3)
+                                             .and_then(|path| path.file_name())
+                                             .and_then(|name| name.to_str())
+                                             .unwrap_or("");
+    return match directory
+    {
+        "debug" => String::from("dev"),
+        "" => var("PROFILE"),
+        name => name.to_string(),
+    };
+}
+
+
+
+// prints the cargo instructions recording the build, from a build script
+pub fn run()
+{
+    let rustc: String = env::var("RUSTC").unwrap_or_else(|_| String::from("rustc"));
+    let rustc_version: String =
+            match Command::new(&rustc).arg("-V").output()
+            {
+                Ok(output) => String::from_utf8_lossy(&output.stdout).trim().to_string(),
+                Err(_) => String::from("unknown"),
+            };
+    let flags: Vec<String> = var("CARGO_ENCODED_RUSTFLAGS").split('\x1f')
+                                                           .filter(|flag| !flag.is_empty())
+                                                           .map(|flag| flag.to_string())
+                                                           .collect();
+    let lock_path = Path::new(&var("CARGO_MANIFEST_DIR")).join("Cargo.lock");
+    let dependencies: Vec<String> =
+            locked_dependencies(&fs::read_to_string(&lock_path).unwrap_or_default());
+
+    println!("cargo:rustc-env=NPB_RUSTC={}", rustc);
+    println!("cargo:rustc-env=NPB_RUSTC_VERSION={}", rustc_version);
+    println!("cargo:rustc-env=NPB_TARGET={}", var("TARGET"));
+    println!("cargo:rustc-env=NPB_TARGET_CPU={}",
+             target_cpu(&flags).unwrap_or_else(|| String::from("(default)")));
+    println!("cargo:rustc-env=NPB_TARGET_FEATURES={}", var("CARGO_CFG_TARGET_FEATURE"));
+    println!("cargo:rustc-env=NPB_PROFILE={} (opt-level={}, debug={})",
+             profile(), var("OPT_LEVEL"), var("DEBUG"));
+    println!("cargo:rustc-env=NPB_RUSTFLAGS={}", flags.join(" "));
+    println!("cargo:rustc-env=NPB_DEPENDENCIES={}", dependencies.join(", "));
+
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=Cargo.lock");
+    println!("cargo:rerun-if-env-changed=RUSTFLAGS");
+    println!("cargo:rerun-if-env-changed=CARGO_ENCODED_RUSTFLAGS");
+}
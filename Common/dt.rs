@@ -0,0 +1,696 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                    Rust version of the benchmark "DT",                    //
+//                       or "Data Traffic" benchmark.                        //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//      The original NPB 3.4.1 version was written in C and belongs to:      //
+//                   http://www.nas.nasa.gov/Software/NPB/                   //
+//                         Authors of the C code is:                         //
+//                                M. Frumkin                                 //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    This benchmark passes feature arrays through a communication graph:    //
+//    sources generate them, comparators filter and combine the arrays they  //
+//    receive, and sinks reduce them to a checksum. The graph is one of the  //
+//    "black hole" (BH: many sources, one sink), "white hole" (WH: one       //
+//    source, many sinks) or "shuffle" (SH: a butterfly network) ones. The   //
+//    nodes run layer by layer, a layer holding the nodes whose inputs all   //
+//    come from the layers before it, and the nodes of a layer are spread    //
+//    over the threads of the backend.                                       //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+#![allow(non_snake_case)]
+
+use std::time::Instant;
+
+use crate::affinity::Binding;
+#[cfg(feature = "rayon")]
+use crate::backend::Rayon;
+use crate::backend::{BackendKind, ParallelBackend, Serial, ThreadsChannels};
+use crate::f64rand;
+use crate::preflight::ClassTable;
+use crate::usage::{ResourceUsage, UsageSampler};
+use crate::verification::Verification;
+
+
+
+pub const FIELDDIM: usize = 4; // number of values of each feature
+pub const TRAN: f64 = 314159265.0; // the seed of the feature counts
+
+// a node of the communication graph
+pub struct Node
+{
+    pub name:    String,
+    pub inputs:  Vec<usize>, // tails of the incoming arcs, in order of attachment
+    pub outputs: Vec<usize>, // heads of the outgoing arcs, in order of attachment
+}
+
+pub struct Graph
+{
+    pub name:     String,
+    pub nodes:    Vec<Node>,
+    pub num_arcs: usize,
+}
+
+impl Graph
+{
+    fn new( name: String ) -> Graph
+    {
+        return Graph { name, nodes: Vec::new(), num_arcs: 0 };
+    }
+
+    // returns the id of the node called 'name', adding it if it does not exist
+    fn attach_node( &mut self, name: String ) -> usize
+    {
+        if let Some(id) = self.nodes.iter().position(|node| node.name == name) { return id; }
+        self.nodes.push(Node { name, inputs: Vec::new(), outputs: Vec::new() });
+        return self.nodes.len() - 1;
+    }
+
+    // adds the arc tail -> head, unless it is already there
+    fn attach_arc( &mut self, tail: &usize, head: &usize )
+    {
+        if self.nodes[*tail].outputs.contains(head) { return; }
+        self.nodes[*tail].outputs.push(*head);
+        self.nodes[*head].inputs.push(*tail);
+        self.num_arcs += 1;
+    }
+
+    // "black hole": sources are merged by layers of comparators into one sink
+    fn build_bh( class_npb: &char, num_sources: &usize ) -> Graph
+    {
+        const MAX_IN_DEGREE: usize = 4;
+        let mut dg: Graph = Graph::new(format!("DT_BH.{}", class_npb));
+        let (mut num_layer_nodes, mut num_prev_layer_nodes): (usize, usize) = (*num_sources, *num_sources);
+        let (mut first_layer_node, mut total_comparators): (usize, usize) = (0, 0);
+
+        for i in 0usize..*num_sources { dg.attach_node(format!("Source.{}", i)); }
+        while num_layer_nodes > MAX_IN_DEGREE
+        {
+            num_layer_nodes /= MAX_IN_DEGREE;
+            if (num_layer_nodes * MAX_IN_DEGREE) < num_prev_layer_nodes { num_layer_nodes += 1; }
+            for i in 0usize..num_layer_nodes
+            {
+                let id: usize = dg.attach_node(format!("Comparator.{}", total_comparators));
+                total_comparators += 1;
+                for j in 0usize..MAX_IN_DEGREE
+                {
+                    let sid: usize = (i * MAX_IN_DEGREE) + j;
+                    if sid >= num_prev_layer_nodes { break; }
+                    dg.attach_arc(&(first_layer_node + sid), &id);
+                }
+            }
+            first_layer_node += num_prev_layer_nodes;
+            num_prev_layer_nodes = num_layer_nodes;
+        }
+        let sink: usize = dg.attach_node(String::from("Sink"));
+        for i in 0usize..num_prev_layer_nodes { dg.attach_arc(&(first_layer_node + i), &sink); }
+
+        return dg;
+    }
+
+    // "white hole": one source is spread by layers of comparators over the sinks
+    fn build_wh( class_npb: &char, num_sources: &usize ) -> Graph
+    {
+        const MAX_IN_DEGREE: usize = 4;
+        let mut dg: Graph = Graph::new(format!("DT_WH.{}", class_npb));
+        let (mut num_layer_nodes, mut num_prev_layer_nodes): (usize, usize) = (*num_sources, *num_sources);
+        let (mut first_layer_node, mut total_comparators): (usize, usize) = (0, 0);
+
+        for i in 0usize..*num_sources { dg.attach_node(format!("Sink.{}", i)); }
+        while num_layer_nodes > MAX_IN_DEGREE
+        {
+            num_layer_nodes /= MAX_IN_DEGREE;
+            if (num_layer_nodes * MAX_IN_DEGREE) < num_prev_layer_nodes { num_layer_nodes += 1; }
+            for i in 0usize..num_layer_nodes
+            {
+                let id: usize = dg.attach_node(format!("Comparator.{}", total_comparators));
+                total_comparators += 1;
+                for j in 0usize..MAX_IN_DEGREE
+                {
+                    let sid: usize = (i * MAX_IN_DEGREE) + j;
+                    if sid >= num_prev_layer_nodes { break; }
+                    dg.attach_arc(&id, &(first_layer_node + sid));
+                }
+            }
+            first_layer_node += num_prev_layer_nodes;
+            num_prev_layer_nodes = num_layer_nodes;
+        }
+        let source: usize = dg.attach_node(String::from("Source"));
+        for i in 0usize..num_prev_layer_nodes { dg.attach_arc(&source, &(first_layer_node + i)); }
+
+        // topological sorting (the nodes were attached from the sinks up)
+        let last: usize = dg.nodes.len() - 1;
+        dg.nodes.reverse();
+        for node in dg.nodes.iter_mut()
+        {
+            for id in node.inputs.iter_mut().chain(node.outputs.iter_mut()) { *id = last - *id; }
+        }
+
+        return dg;
+    }
+
+    // "shuffle": sources and sinks connected by a butterfly network of comparators
+    fn build_sh( class_npb: &char, num_sources: &usize ) -> Graph
+    {
+        let mut dg: Graph = Graph::new(format!("DT_SH.{}", class_npb));
+        let (mut num_of_layers, mut tmp_s): (usize, usize) = (0, num_sources >> 1);
+        let mut first_layer_node: usize = 0;
+
+        while tmp_s > 1
+        {
+            num_of_layers += 1;
+            tmp_s >>= 1;
+        }
+        for i in 0usize..*num_sources { dg.attach_node(format!("Source.{}", i)); }
+        for j in 0usize..num_of_layers
+        {
+            let mask: usize = 1 << j;
+            for i in 0usize..*num_sources
+            {
+                let id: usize = dg.attach_node(format!("Comparator.{}", i + (j * first_layer_node)));
+                let ndoff: usize = i & !mask;
+                dg.attach_arc(&(first_layer_node + ndoff), &id);
+                dg.attach_arc(&(first_layer_node + ndoff + mask), &id);
+            }
+            first_layer_node += num_sources;
+        }
+        let mask: usize = 1 << num_of_layers;
+        for i in 0usize..*num_sources
+        {
+            let id: usize = dg.attach_node(format!("Sink.{}", i));
+            let ndoff: usize = i & !mask;
+            dg.attach_arc(&(first_layer_node + ndoff), &id);
+            dg.attach_arc(&(first_layer_node + ndoff + mask), &id);
+        }
+
+        return dg;
+    }
+
+    pub fn show( &self )
+    {
+        println!(" {}: {} nodes, {} arcs", self.name, self.nodes.len(), self.num_arcs);
+        for (id, node) in self.nodes.iter().enumerate()
+        {
+            let inputs: Vec<String> = node.inputs.iter().map(|i| i.to_string()).collect();
+            println!("    {:>4}  {:<16} <- [{}]", id, node.name, inputs.join(", "));
+        }
+    }
+}
+
+
+
+// a^n (mod md), with the 32-bit (wrapping) arithmetic of the original code
+fn ipow_mod( a: &i32, n: &i64, md: &i32 ) -> i32
+{
+    let (mut q, mut r, mut n): (i32, i32, i64) = (*a, 1, *n);
+
+    if n == 0 { return 1; }
+    while n > 1
+    {
+        if (n % 2) == 0
+        {
+            q = q.wrapping_mul(q) % md;
+            n /= 2;
+        }
+        else
+        {
+            r = r.wrapping_mul(q) % md;
+            n -= 1;
+        }
+    }
+    return r.wrapping_mul(q) % md;
+}
+
+// the number of features generated by source 'id'
+fn get_feature_num( id: &usize, num_samples: &usize, std_deviation: &usize ) -> usize
+{
+    let mut tran: f64 = TRAN;
+    let a: f64 = ((2 * id) + 1) as f64;
+    let denom: f64 = f64rand::randlc(&mut tran, &a);
+    let cval: i32 = 'S' as i32;
+
+    let rtfs: i32 = ipow_mod(&((1.0 / denom) as i32).wrapping_mul(cval), &(a as i64),
+                             &(2 * (*std_deviation as i32))).abs();
+    return (num_samples - std_deviation) + (rtfs as usize);
+}
+
+// the feature array generated by source 'id'
+fn random_features( id: &usize, num_samples: &usize, std_deviation: &usize ) -> Vec<f64>
+{
+    let len: usize = get_feature_num(id, num_samples, std_deviation) * FIELDDIM;
+    let mut feat: Vec<f64> = vec![0.0; len];
+    let (nxg, nyg, nzg, nfg): (i32, i32, i32, i32) = (2, 2, 2, 5);
+    let (nx, ny, nz, nf): (i32, i32, i32, i32) = (421, 419, 1427, 3527);
+    let expon: i64 = ((len * (id + 1)) % 3141592) as i64;
+    let (mut seedx, mut seedy, mut seedz, mut seedf): (i32, i32, i32, i32) =
+            (ipow_mod(&nxg, &expon, &nx), ipow_mod(&nyg, &expon, &ny),
+             ipow_mod(&nzg, &expon, &nz), ipow_mod(&nfg, &expon, &nf));
+
+    for i in (0usize..len).step_by(FIELDDIM)
+    {
+        seedx = (seedx * nxg) % nx;
+        seedy = (seedy * nyg) % ny;
+        seedz = (seedz * nzg) % nz;
+        seedf = (seedf * nfg) % nf;
+        feat[i] = seedx as f64;
+        feat[i + 1] = seedy as f64;
+        feat[i + 2] = seedz as f64;
+        feat[i + 3] = seedf as f64;
+    }
+
+    return feat;
+}
+
+// stretches 'a' to 'blen' values
+fn resample( a: &mut Vec<f64>, blen: &usize )
+{
+    let mut nval: Vec<f64> = vec![0.0; *blen];
+    let ratio: usize = blen / a.len();
+
+    for (i, a_i) in a.iter().enumerate().take(a.len() - 1).skip(1)
+    {
+        let jlo: usize = (0.5 * (((2 * i) - 1) * ratio) as f64) as usize;
+        let jhi: usize = (0.5 * (((2 * i) + 1) * ratio) as f64) as usize;
+        let avval: f64 = a_i / ((jhi - jlo + 1) as f64);
+        for nval_j in nval[jlo..=jhi].iter_mut() { *nval_j += avval; }
+    }
+    nval[0] = a[0];
+    nval[blen - 1] = a[a.len() - 1];
+    *a = nval;
+}
+
+/* replaces each feature of 'a' by the weighted feature of 'b' nearest to it
+ * (among the ones at the same, previous and next positions)
+ */
+fn window_filter( a: &mut Vec<f64>, b: &mut Vec<f64>, w: &usize )
+{
+    let weight: f64 = ((w + 1) as f64) / ((w + 2) as f64);
+    let distance = |a: &[f64], b: &[f64], i: usize, j: usize| -> f64
+    {
+        return (0usize..FIELDDIM).map(|f| (a[i + f] - b[j + f]) * (a[i + f] - b[j + f])).sum();
+    };
+
+    if a.len() < b.len() { resample(a, &b.len()); }
+    if a.len() > b.len() { resample(b, &a.len()); }
+
+    let mut i: usize = FIELDDIM;
+    while (i + FIELDDIM) < a.len()
+    {
+        let mut rms0: f64 = distance(a, b, i, i);
+        let rms1: f64 = distance(a, b, i + FIELDDIM, i + FIELDDIM);
+        let rmsm1: f64 = distance(a, b, i - FIELDDIM, i - FIELDDIM);
+        let mut j: usize = i;
+
+        if rms1 < rms0
+        {
+            j = i + FIELDDIM;
+            rms0 = rms1;
+        }
+        if rmsm1 < rms0 { j = i - FIELDDIM; }
+        for f in 0usize..FIELDDIM { a[i + f] = weight * b[j + f]; }
+
+        i += FIELDDIM;
+    }
+}
+
+fn check_val( feat: &[f64] ) -> f64
+{
+    let len: f64 = feat.len() as f64;
+    return feat.iter().map(|v| (v * v) / len).sum();
+}
+
+fn reduce( a: &[f64], w: &usize ) -> f64
+{
+    // the truncation is needed for node and array dependent verification
+    return (((*w as f64) * check_val(a)) as i32) as f64;
+}
+
+// what a comparator does with the feature arrays of its incoming arcs
+fn combine_streams( inputs: &[&[f64]], id: &usize, num_samples: &usize ) -> Vec<f64>
+{
+    let mut resfeat: Vec<f64> = vec![0.0; num_samples * FIELDDIM];
+
+    for input in inputs
+    {
+        let mut feat: Vec<f64> = input.to_vec();
+        window_filter(&mut resfeat, &mut feat, id);
+    }
+    for v in resfeat.iter_mut() { *v = ((*v as i32) / (inputs.len() as i32)) as f64; }
+
+    return resfeat;
+}
+
+// what a sink does with the feature arrays of its incoming arcs
+fn reduce_streams( inputs: &[&[f64]], id: &usize ) -> f64
+{
+    let mut csum: f64 = 0.0;
+
+    for input in inputs { csum += reduce(input, &(id + 1)); }
+    if !inputs.is_empty() { csum = ((csum as i64) / (inputs.len() as i64)) as f64; }
+
+    return ((id + 1) as f64) * csum;
+}
+
+/* the layers of the graph: the sources, then every node whose inputs all
+ * are in the layers before it (the nodes are in topological order)
+ */
+fn layers( dg: &Graph ) -> Vec<Vec<usize>>
+{
+    let mut depth: Vec<usize> = vec![0; dg.nodes.len()];
+    let mut layers: Vec<Vec<usize>> = Vec::new();
+
+    for (id, node) in dg.nodes.iter().enumerate()
+    {
+        depth[id] = node.inputs.iter().map(|tail| depth[*tail] + 1).max().unwrap_or(0);
+        if depth[id] == layers.len() { layers.push(Vec::new()); }
+        layers[depth[id]].push(id);
+    }
+
+    return layers;
+}
+
+/* runs every node of the graph, layer by layer, the nodes of a layer being
+ * spread over the threads of 'backend'; returns the sum of the checksums of
+ * the sinks
+ */
+fn process_nodes<B: ParallelBackend>( backend: &B, dg: &Graph, num_samples: &usize,
+                                      std_deviation: &usize ) -> f64
+{
+    let mut features: Vec<Vec<f64>> = vec![Vec::new(); dg.nodes.len()];
+    let mut chksum: f64 = 0.0;
+
+    for layer in layers(dg).iter()
+    {
+        // the feature array (sources and comparators) or the checksum (sinks) of every node
+        let outputs: Vec<(Vec<f64>, f64)> = backend.map_reduce(&layer.len(), |range|
+        {
+            return layer[range].iter().map(|id|
+            {
+                let node: &Node = &dg.nodes[*id];
+                let inputs: Vec<&[f64]> =
+                        node.inputs.iter().map(|tail| features[*tail].as_slice()).collect();
+                if node.name.contains("Source")
+                {
+                    return (random_features(id, num_samples, std_deviation), 0.0);
+                }
+                if node.name.contains("Sink") { return (Vec::new(), reduce_streams(&inputs, id)); }
+                return (combine_streams(&inputs, id, num_samples), 0.0);
+            }).collect::<Vec<(Vec<f64>, f64)>>();
+        },
+        |mut outputs, more| { outputs.extend(more); return outputs; });
+
+        for (id, (feat, sink_chksum)) in layer.iter().zip(outputs)
+        {
+            features[*id] = feat;
+            chksum += sink_chksum;
+        }
+    }
+
+    return chksum;
+}
+
+
+
+// the samples, their standard deviation and the sources of class 'class_npb'
+pub fn class_size( class_npb: &char ) -> Option<(usize, usize, usize)>
+{
+    return match class_npb
+    {
+        'S' => Some((1728, 128, 4)),
+        'W' => Some((1728 * 8, 128 * 2, 4 * 2)),
+        'A' => Some((1728 * 64, 128 * 4, 4 * 4)),
+        'B' => Some((1728 * 512, 128 * 8, 4 * 8)),
+        'C' => Some((1728 * 4096, 128 * 16, 4 * 16)),
+        'D' => Some((1728 * 32768, 128 * 32, 4 * 32)),
+        _ => None,
+    };
+}
+
+// the reference checksum of the official NPB for 'graph' (BH, WH or SH) of class 'class_npb'
+pub fn reference_value( class_npb: &char, graph: &str ) -> Option<f64>
+{
+    return match (*class_npb, graph)
+    {
+        ('S', "BH") => Some(30892725.0),
+        ('S', "WH") => Some(67349758.0),
+        ('S', "SH") => Some(58875767.0),
+        ('W', "BH") => Some(4102461.0),
+        ('W', "WH") => Some(204280762.0),
+        ('W', "SH") => Some(186944764.0),
+        ('A', "BH") => Some(17809491.0),
+        ('A', "WH") => Some(1289925229.0),
+        ('A', "SH") => Some(610856482.0),
+        ('B', "BH") => Some(4317114.0),
+        ('B', "WH") => Some(7877279917.0),
+        ('B', "SH") => Some(1836863082.0),
+        _ => None,
+    };
+}
+
+pub const EPSILON: f64 = 1.0e-8;
+
+pub fn verify( class_npb: &char, graph: &str, rnm2: &f64 ) -> Verification
+{
+    return match reference_value(class_npb, graph)
+    {
+        Some(verify_value) => Verification::from((rnm2 - verify_value).abs() < EPSILON),
+        None => Verification::NotPerformed,
+    };
+}
+
+
+
+// how to run DT
+#[derive(Clone, Debug)]
+pub struct Config
+{
+    pub class:   char,
+    pub graph:   String,        // BH, WH or SH
+    pub backend: BackendKind,
+    pub threads: Option<usize>, // the backend's default if None
+    pub binding: Binding,       // of the backend's threads
+    pub perf:    bool,          // counts the hardware events of the timed run
+}
+
+impl Config
+{
+    // a run of class 'class_npb' on 'graph', on 'backend' with its default threads
+    pub fn new( class_npb: &char, graph: &str, backend: &BackendKind ) -> Config
+    {
+        return Config
+        {
+            class: *class_npb, graph: String::from(graph), backend: *backend, threads: None,
+            binding: Binding::None, perf: false,
+        };
+    }
+}
+
+impl Graph
+{
+    // the graph of the run 'config' describes
+    pub fn of( config: &Config ) -> Result<Graph, String>
+    {
+        let (_, _, num_sources): (usize, usize, usize) =
+                class_size(&config.class).ok_or(format!("DT has no class {}", config.class))?;
+        return match config.graph.as_str()
+        {
+            "BH" => Ok(Graph::build_bh(&config.class, &num_sources)),
+            "WH" => Ok(Graph::build_wh(&config.class, &num_sources)),
+            "SH" => Ok(Graph::build_sh(&config.class, &num_sources)),
+            graph => Err(format!("DT has no graph \"{}\" (only BH, WH and SH)", graph)),
+        };
+    }
+}
+
+// the class table of DT: the samples and sources of a class, on one of the graphs
+#[derive(Clone, Debug)]
+pub struct DtClass
+{
+    pub class:         char,
+    pub graph:         String,
+    pub nodes:         usize,
+    pub num_samples:   usize,
+    pub std_deviation: usize,
+    pub threads:       usize,
+    pub arrays:        usize, // feature arrays alive at once
+}
+
+impl DtClass
+{
+    // the entry of the run 'config' describes
+    pub fn of( config: &Config ) -> Result<DtClass, String>
+    {
+        let dg: Graph = Graph::of(config)?;
+        let (num_samples, std_deviation, _): (usize, usize, usize) =
+                class_size(&config.class).ok_or(format!("DT has no class {}", config.class))?;
+        let threads: usize = config.backend.threads(&config.threads);
+        return Ok(DtClass
+        {
+            class: config.class, graph: dg.name.clone(), nodes: dg.nodes.len(), num_samples,
+            std_deviation, threads,
+            // every node keeps its array until the end, and every thread copies the one it filters
+            arrays: dg.nodes.len() + threads,
+        });
+    }
+}
+
+impl ClassTable for DtClass
+{
+    fn kernel( &self ) -> &'static str
+    {
+        return "DT";
+    }
+
+    fn class( &self ) -> char
+    {
+        return self.class;
+    }
+
+    fn size( &self ) -> String
+    {
+        return format!("{} samples, {} ({} nodes)", self.num_samples, self.graph, self.nodes);
+    }
+
+    // the nodes, each processed once
+    fn iterations( &self ) -> usize
+    {
+        return self.nodes;
+    }
+
+    // a feature array holds up to NUM_SAMPLES + STD_DEVIATION features
+    fn working_set( &self ) -> u64
+    {
+        return (self.arrays * (self.num_samples + self.std_deviation) * FIELDDIM *
+                std::mem::size_of::<f64>()) as u64;
+    }
+
+    fn seeds( &self ) -> Vec<(&'static str, f64)>
+    {
+        return vec![("tran", TRAN)];
+    }
+}
+
+// the results of a run of DT
+#[derive(Clone, Debug)]
+pub struct DtResult
+{
+    pub class:        char,
+    pub graph:        String,       // with the class, as in "DT_BH.S"
+    pub nodes:        usize,
+    pub arcs:         usize,
+    pub featnum:      usize,        // the values of the arrays sent on each arc
+    pub backend:      &'static str,
+    pub threads:      usize,
+    pub chksum:       f64,
+    pub time:         f64,
+    pub Mops:         f64,          // millions of bytes transmitted per second
+    pub verification: Verification,
+    pub usage:        ResourceUsage,
+}
+
+impl DtResult
+{
+    // the comparison with the reference checksum, as dt.c prints it
+    pub fn comparison( &self ) -> String
+    {
+        let mut out: String = format!(" {} L2 Norm = {:.6}\n", self.graph, self.chksum);
+        match reference_value(&self.class, &self.graph[3..5])
+        {
+            None => out += " No verification was performed.\n",
+            Some(verify_value) if self.verification.passed() =>
+                out += &format!(" Deviation = {:.6}\n", self.chksum - verify_value),
+            Some(verify_value) =>
+            {
+                out += &format!(" The correct verification value = {:.6}\n", verify_value);
+                out += &format!(" Got value = {:.6}\n", self.chksum);
+            }
+        }
+        return out;
+    }
+}
+
+// runs DT through 'backend' as 'config' says, on the graph 'dg'
+fn run_on<B: ParallelBackend>( backend: &B, config: &Config, table: &DtClass,
+                               dg: Graph ) -> DtResult
+{
+    // starting main timer
+    let sampler: UsageSampler = UsageSampler::start(&config.perf);
+    let start_moment: Instant = Instant::now();
+
+    let chksum: f64 = process_nodes(backend, &dg, &table.num_samples, &table.std_deviation);
+
+    // computing final stats
+    let time: f64 = (start_moment.elapsed().as_micros() as f64) / 1e6;
+    let usage: ResourceUsage = sampler.stop();
+    let featnum: usize = table.num_samples * FIELDDIM;
+    let bytes_sent: f64 = ((featnum * dg.num_arcs) as f64) / 1048576.0;
+
+    return DtResult
+    {
+        class:        table.class,
+        verification: verify(&table.class, &config.graph, &chksum),
+        graph:        dg.name,
+        nodes:        dg.nodes.len(),
+        arcs:         dg.num_arcs,
+        featnum,
+        backend:      backend.name(),
+        threads:      backend.num_threads(),
+        chksum,
+        time,
+        Mops:         bytes_sent / time,
+        usage,
+    };
+}
+
+/* runs DT as 'config' says, without printing anything; an error if its class
+ * or graph is unknown, or its backend unavailable
+ */
+pub fn run( config: &Config ) -> Result<DtResult, String>
+{
+    let table: DtClass = DtClass::of(config)?;
+    let dg: Graph = Graph::of(config)?;
+
+    return match config.backend
+    {
+        BackendKind::Serial => Ok(run_on(&Serial, config, &table, dg)),
+        BackendKind::ThreadsChannels =>
+            Ok(run_on(&ThreadsChannels::with_binding(&table.threads, &config.binding), config,
+                      &table, dg)),
+        #[cfg(feature = "rayon")]
+        BackendKind::Rayon => Ok(run_on(&Rayon::with_binding(&config.threads, &config.binding),
+                                        config, &table, dg)),
+        #[cfg(not(feature = "rayon"))]
+        BackendKind::Rayon => Err(String::from("the Rayon backend needs the \"rayon\" feature")),
+    };
+}
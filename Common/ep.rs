@@ -0,0 +1,653 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//   The original NPB 3.4.1 version was written in Fortran and belongs to:   //
+//                   http://www.nas.nasa.gov/Software/NPB/                   //
+//                     Authors of the Fortran code are:                      //
+//           P. O. Frederickson, D. H. Bailey, A. C. Woo, H. Jin             //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    The kernel of EP, the "embarrassingly parallel" benchmark, written     //
+//    once for every backend: the batches of random numbers are spread over  //
+//    the backend's threads, and their sums and counts reduced at the end.   //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+#![allow(non_snake_case)]
+
+use std::time::Instant;
+
+use crate::f64rand;
+#[cfg(feature = "rayon")]
+use crate::backend::Rayon;
+use crate::affinity::Binding;
+use crate::backend::{BackendKind, ParallelBackend, Serial, ThreadsChannels};
+use crate::preflight::ClassTable;
+use crate::report;
+use crate::scaling::ScalingRun;
+use crate::stats;
+use crate::trace::{self, Span, MAIN_THREAD};
+use crate::usage::{ResourceUsage, UsageSampler};
+use crate::verification::Verification;
+
+
+
+pub const MK:      usize = 16; // log2 of the size of each batch of random numbers
+pub const NK:      usize = 1 << MK;
+pub const NQ:      usize = 10;
+pub const A:       f64   = 1220703125.0;
+pub const S:       f64   = 271828183.0;
+pub const NK_PLUS: usize = (2 * NK) + 1; // 2NK + 1
+
+// what one worker (one range of batches) did, in seconds since the run started
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WorkerTimes
+{
+    pub start:         f64,
+    pub end:           f64,
+    pub batches:       usize,
+    pub time_random:   f64,       // if timed
+    pub time_gaussian: f64,       // if timed
+    pub spans:         Vec<Span>, // of its batches, if traced (until taken by take_spans)
+}
+
+impl WorkerTimes
+{
+    pub fn busy( &self ) -> f64
+    {
+        return self.end - self.start;
+    }
+}
+
+// what every batch (and then, the whole run) adds up
+#[derive(Clone, Default)]
+pub struct EpSums
+{
+    pub sx:      f64,
+    pub sy:      f64,
+    pub q:       [f64; NQ],
+    pub workers: Vec<WorkerTimes>, // in the order of their ranges
+    pub spans:   Vec<Span>,        // of the main thread, if traced
+}
+
+impl EpSums
+{
+    fn new() -> EpSums
+    {
+        return EpSums { sx: 0.0, sy: 0.0, q: [0.0; NQ], workers: Vec::new(), spans: Vec::new() };
+    }
+
+    // moves every span out, the ones of the workers put on threads 1, 2, ...
+    pub fn take_spans( &mut self ) -> Vec<Span>
+    {
+        let mut spans: Vec<Span> = std::mem::take(&mut self.spans);
+        for (i, worker) in self.workers.iter_mut().enumerate()
+        {
+            spans.extend(std::mem::take(&mut worker.spans).into_iter()
+                                                          .map(|span| Span { thread: i + 1, ..span }));
+        }
+        return spans;
+    }
+
+    fn add( mut self, other: EpSums ) -> EpSums
+    {
+        self.sx += other.sx;
+        self.sy += other.sy;
+        for i in 0usize..NQ { self.q[i] += other.q[i]; }
+        self.workers.extend(other.workers);
+        return self;
+    }
+}
+
+
+
+// M (log2 of the number of pairs of random numbers) of class 'class_npb'
+pub fn class_size( class_npb: &char ) -> Option<usize>
+{
+    return match class_npb
+    {
+        'S' => Some(24),
+        'W' => Some(25),
+        'A' => Some(28),
+        'B' => Some(30),
+        'C' => Some(32),
+        'D' => Some(36),
+        'E' => Some(40),
+        _ => None,
+    };
+}
+
+// the reference sums (sx, sy) of the official NPB for a problem of 2^(M+1) random numbers
+// (kept with all the digits ep.f gives them)
+#[allow(clippy::excessive_precision)]
+pub fn reference_sums( M: &usize ) -> Option<(f64, f64)>
+{
+	return match *M
+	{
+		24 => Some((-3.247834652034740e+3, -6.958407078382297e+3)),
+		25 => Some((-2.863319731645753e+3, -6.320053679109499e+3)),
+		28 => Some((-4.295875165629892e+3, -1.580732573678431e+4)),
+		30 => Some(( 4.033815542441498e+4, -2.660669192809235e+4)),
+		32 => Some(( 4.764367927995374e+4, -8.084072988043731e+4)),
+		36 => Some(( 1.982481200946593e+5, -1.020596636361769e+5)),
+		40 => Some((-5.319717441530e+05, -3.688834557731e+05)),
+		_ => None,
+	};
+}
+
+pub fn verify( M: &usize, sx: &f64, sy: &f64 ) -> Verification
+{
+    const EPSILON: f64  = 1.0e-8; // random numbers precision
+
+	let (sx_verify_value, sy_verify_value): (f64, f64) = match reference_sums(M)
+	{
+		Some(sums) => sums,
+		None => return Verification::NotPerformed,
+	};
+
+	return Verification::from((((sx - sx_verify_value) / sx_verify_value).abs() <= EPSILON) &&
+	                          (((sy - sy_verify_value) / sy_verify_value).abs() <= EPSILON));
+}
+
+
+
+// AN = A^(2NK) (mod 2^46): the multiplier jumping over the numbers of a batch
+pub fn batch_multiplier() -> f64
+{
+    let (mut t1, mut t2): (f64, f64);
+
+	t1 = A;
+	for _i in 0usize..(MK + 1)
+	{
+	    t2 = t1;
+	    f64rand::randlc(&mut t1, &t2);
+	}
+	return t1;
+}
+
+/* the seed of batch 'k' (0-based): S jumped ahead over the 2NK numbers of
+ * every batch before it, 'an' being batch_multiplier()
+ */
+pub fn batch_seed( k: &usize, an: &f64 ) -> f64
+{
+    let (mut t1, mut t2, mut t4): (f64, f64, f64);
+    let (mut kk, mut ik): (i64, i64);
+
+    kk = *k as i64;
+    t1 = S;
+    t2 = *an;
+
+    /* find starting seed t1 for this kk */
+    for _i in 1usize..=100usize
+    {
+        ik = kk / 2;
+        if (2 * ik) != kk { f64rand::randlc(&mut t1, &t2); }
+        if ik == 0 { break; }
+        t4 = t2;
+        f64rand::randlc(&mut t2, &t4);
+        kk = ik;
+    }
+
+    return t1;
+}
+
+/* compute gaussian deviates by acceptance-rejection method and tally counts
+ * in concentric square annuli, from the NK pairs of uniform numbers in 'x'.
+ * this loop is not vectorizable.
+ */
+pub fn tally_gaussian_pairs( x: &[f64], sums: &mut EpSums )
+{
+	let (mut x1, mut x2, mut t1, mut t2, mut t3, mut t4): (f64, f64, f64, f64, f64, f64);
+
+    for i in 0usize..NK
+    {
+        x1 = 2.0 * x[2 * i] - 1.0;
+        x2 = 2.0 * x[(2 * i) + 1] - 1.0;
+        t1 = (x1 * x1) + (x2 * x2);
+        if t1 <= 1.0
+        {
+	        t2 = (-2.0 * t1.ln() / t1).sqrt();
+	        t3 = x1 * t2; // Xi
+	        t4 = x2 * t2; // Yi
+	        sums.q[f64::max(t3.abs(), t4.abs()) as usize] += 1.0; // count
+	        sums.sx += t3; // sum of Xi
+	        sums.sy += t4; // sum of Yi
+        }
+    }
+}
+
+/* the whole of batch 'k' (its seed, uniform numbers and gaussian pairs),
+ * tallied into 'sums', 'x' being a buffer of NK_PLUS numbers; 'lap' is called
+ * at the end of the jump-ahead and of the generation of the numbers, for the
+ * timers and the trace (a no-op closure when they are off)
+ */
+pub fn run_batch<L: FnMut()>( k: &usize, an: &f64, x: &mut [f64], sums: &mut EpSums, lap: &mut L )
+{
+    let mut seed: f64 = batch_seed(k, an);
+    lap();
+
+    /* compute uniform pseudorandom numbers */
+    f64rand::nrandlc(&(2 * NK), &mut seed, &A, x);
+    lap();
+
+    tally_gaussian_pairs(x, sums);
+}
+
+/* generates and tallies the batches in 'batches' (0-based), timing them
+ * against 'origin', and recording their spans if traced ('trace_offset' being
+ * the seconds between the start of the trace and 'origin')
+ */
+fn run_batches( batches: std::ops::Range<usize>, an: &f64, origin: &Instant, use_timers: &bool,
+                trace_offset: &Option<f64> ) -> EpSums
+{
+    let mut sums: EpSums = EpSums::new();
+    let mut worker: WorkerTimes = WorkerTimes
+    {
+        start: trace::since(origin), batches: batches.len(), ..WorkerTimes::default()
+    };
+    let mut x: Vec<f64> = vec![0.0; NK_PLUS];
+    let timed: bool = *use_timers || trace_offset.is_some();
+    let (mut batch_start, mut batch_end): (f64, f64) = (0.0, 0.0);
+
+    for k in batches
+    {
+        // the ends of the jump-ahead and of the generation of the numbers
+        let (mut laps, mut lap): ([f64; 2], usize) = ([0.0; 2], 0);
+        if timed { batch_start = trace::since(origin); }
+        run_batch(&k, an, &mut x, &mut sums, &mut ||
+        {
+            if timed { laps[lap] = trace::since(origin); lap += 1; }
+        });
+        let [seeded, generated]: [f64; 2] = laps;
+
+        if timed
+        {
+            batch_end = trace::since(origin);
+            worker.time_random += generated - batch_start;
+            worker.time_gaussian += batch_end - generated;
+        }
+        if let Some(offset) = trace_offset
+        {
+            let [start, seeded, generated, end]: [f64; 4] =
+                    [batch_start, seeded, generated, batch_end].map(|t| offset + t);
+            worker.spans.push(Span::new(&format!("batch {}", k), "batch", &0, &start, &end));
+            worker.spans.push(Span::new("jump-ahead", "rng", &0, &start, &seeded));
+            worker.spans.push(Span::new("random numbers", "rng", &0, &seeded, &generated));
+            worker.spans.push(Span::new("gaussian pairs", "compute", &0, &generated, &end));
+        }
+    }
+
+    worker.end = trace::since(origin);
+    sums.workers.push(worker);
+    return sums;
+}
+
+/* generates the 2^(M+1) random numbers of EP through 'backend', returning
+ * their sums and counts (and their spans, if a trace started at 'trace')
+ */
+pub fn compute<B: ParallelBackend>( backend: &B, M: &usize, use_timers: &bool,
+                                    trace: &Option<Instant> ) -> EpSums
+{
+    /* number of "batches" of random number pairs generated */
+    let total_batches: usize = 1 << (M - MK); // 2^(M-MK)
+    let origin: Instant = Instant::now();
+    let trace_offset: Option<f64> = trace.map(|start| origin.duration_since(start).as_secs_f64());
+
+	// compute AN = A ^ (2 * NK) (mod 2^46)
+    let an: f64 = batch_multiplier();
+    let spread: f64 = trace::since(&origin);
+
+    /* each batch may be performed independently, so they are spread over
+     * the threads of the backend
+     */
+    let mut sums: EpSums =
+            backend.map_reduce(&total_batches,
+                               |batches| run_batches(batches, &an, &origin, use_timers, &trace_offset),
+                               EpSums::add);
+
+    if let Some(offset) = trace_offset
+    {
+        let join: f64 = sums.workers.iter().map(|w| w.end).fold(spread, f64::max);
+        sums.spans.push(Span::new("AN = A^(2NK)", "init", &MAIN_THREAD, &offset, &(offset + spread)));
+        sums.spans.push(Span::new("join and reduction", "reduction", &MAIN_THREAD, &(offset + join),
+                                  &(offset + trace::since(&origin))));
+    }
+    return sums;
+}
+
+
+
+// how to run EP
+#[derive(Clone, Debug)]
+pub struct Config
+{
+    pub class:      char,
+    pub M:          Option<usize>, // overrides the size of 'class' if given
+    pub backend:    BackendKind,
+    pub threads:    Option<usize>, // the backend's default if None
+    pub binding:    Binding,       // of the backend's threads
+    pub use_timers: bool,
+    pub warmup:     usize,         // discarded runs before the timed ones
+    pub repeat:     usize,         // timed runs (at least one)
+    pub trace:      bool,          // records the spans of the runs (EpResult::trace)
+    pub perf:       bool,          // counts the hardware events of the timed runs
+}
+
+impl Config
+{
+    // a single run of class 'class_npb', on 'backend' with its default threads
+    pub fn new( class_npb: &char, backend: &BackendKind ) -> Config
+    {
+        return Config
+        {
+            class: *class_npb, M: None, backend: *backend, threads: None,
+            binding: Binding::None, use_timers: false, warmup: 0, repeat: 1, trace: false,
+            perf: false,
+        };
+    }
+}
+
+// the entry of EP's class table for a run: its size, and how many threads share it
+#[derive(Clone, Copy, Debug)]
+pub struct EpClass
+{
+    pub class:   char,
+    pub M:       usize,
+    pub threads: usize,
+}
+
+impl EpClass
+{
+    /* the entry of the run 'config' describes (of its class, or of its own M,
+     * which must give at least one batch, and a count of batches that fits in
+     * a usize)
+     */
+    pub fn of( config: &Config ) -> Result<EpClass, String>
+    {
+        let largest: usize = MK + (usize::BITS as usize) - 1;
+        return match config.M.or_else(|| class_size(&config.class))
+        {
+            Some(M) if (M < MK) || (M > largest) =>
+                Err(format!("EP's M must be between {} and {}, not {}", MK, largest, M)),
+            Some(M) => Ok(EpClass
+                       {
+                           class: config.class, M,
+                           threads: config.backend.threads(&config.threads),
+                       }),
+            None => Err(format!("EP has no class {}", config.class)),
+        };
+    }
+}
+
+impl ClassTable for EpClass
+{
+    fn kernel( &self ) -> &'static str
+    {
+        return "EP";
+    }
+
+    fn class( &self ) -> char
+    {
+        return self.class;
+    }
+
+    fn size( &self ) -> String
+    {
+        return format!("{} random numbers", 1u128 << (self.M + 1));
+    }
+
+    // the batches of random numbers
+    fn iterations( &self ) -> usize
+    {
+        return 1 << self.M.saturating_sub(MK);
+    }
+
+    // a batch buffer and the sums per thread, and the buffer touched before timing
+    fn working_set( &self ) -> u64
+    {
+        let buffer: usize = NK_PLUS * std::mem::size_of::<f64>();
+        return (((self.threads + 1) * buffer) +
+                (self.threads * std::mem::size_of::<EpSums>())) as u64;
+    }
+
+    fn seeds( &self ) -> Vec<(&'static str, f64)>
+    {
+        return vec![("S", S), ("A", A)];
+    }
+}
+
+// the results of a run of EP (of the last of them, for the sums and counts)
+#[derive(Clone, Debug)]
+pub struct EpResult
+{
+    pub class:          char,
+    pub M:              usize,
+    pub backend:        &'static str,
+    pub threads:        usize,
+    pub binding:        String,            // as applied to the threads
+    pub sx:             f64,
+    pub sy:             f64,
+    pub q:              [f64; NQ],
+    pub gaussian_pairs: f64,
+    pub seconds:        Vec<f64>,          // every timed run
+    pub mops:           Vec<f64>,          // every timed run
+    pub time:           f64,               // the median of 'seconds'
+    pub Mops:           f64,               // the Mop/s of 'time'
+    pub time_gaussian:  f64,               // seconds per thread, if timed
+    pub time_random:    f64,               // seconds per thread, if timed
+    pub workers:        Vec<WorkerTimes>,  // of the last timed run
+    pub trace:          Vec<Span>,         // of every run, if traced
+    pub verification:   Verification,      // of all the timed runs together
+    pub usage:          ResourceUsage,     // of all the timed runs together
+}
+
+impl EpResult
+{
+    // the result as a point of a strong-scaling sweep
+    pub fn scaling_run( &self ) -> ScalingRun
+    {
+        return ScalingRun
+        {
+            threads: self.threads, seconds: self.time, mops: self.Mops,
+            verification: self.verification,
+        };
+    }
+
+    // the EP-specific results block, printed before print_results'
+    pub fn summary( &self ) -> String
+    {
+        let mut out: String = String::new();
+	    out += "\n EP Benchmark Results:\n\n";
+	    out += &format!(" CPU Time           =                {:.4}\n", self.time);
+	    out += &format!(" N                  =                2^{}\n", self.M);
+	    out += &format!(" No. Gaussian Pairs =                {:.0}\n", self.gaussian_pairs);
+	    out += &format!(" Sums               =                {:.15}\n", self.sx);
+	    out += &format!("                                     {:.15}\n", self.sy);
+	    out += " Counts:\n";
+	    for i in 0usize..(NQ - 1) { out += &format!("    {}    {:>15}\n", i, self.q[i] as i64); }
+        return out;
+    }
+
+    // the EP-specific results block as the reference NPB 3.4.1 prints it (ep.f)
+    pub fn summary_npb( &self ) -> String
+    {
+        let mut out: String = String::new();
+	    out += "\n EP Benchmark Results:\n\n";
+	    out += &format!(" CPU Time ={:>10.4}\n", self.time);
+	    out += &format!(" N = 2^{:>5}\n", self.M);
+	    out += &format!(" No. Gaussian Pairs ={}\n", report::fortran_f0(&self.gaussian_pairs, &15));
+	    out += &format!(" Sums = {}{}\n", report::fortran_d(&self.sx, &25, &15),
+	                    report::fortran_d(&self.sy, &25, &15));
+	    out += " Counts:\n";
+	    for i in 0usize..NQ { out += &format!("{:>3}{}\n", i, report::fortran_f0(&self.q[i], &15)); }
+        return out;
+    }
+
+    /* how unevenly the workers were loaded: the ratio of the longest busy time
+     * to the mean one, and the time the workers spent idle (summed), waiting
+     * at the join for the last one to end
+     */
+    pub fn imbalance( &self ) -> (f64, f64)
+    {
+        if self.workers.is_empty() { return (1.0, 0.0); }
+        let busy: Vec<f64> = self.workers.iter().map(|w| w.busy()).collect();
+        let mean: f64 = busy.iter().sum::<f64>() / (busy.len() as f64);
+        let max: f64 = busy.iter().cloned().fold(0.0, f64::max);
+        let join: f64 = self.workers.iter().map(|w| w.end).fold(0.0, f64::max);
+        let idle: f64 = self.workers.iter().map(|w| join - w.end).sum();
+        return (if mean > 0.0 { max / mean } else { 1.0 }, idle);
+    }
+
+    /* the timeline of every worker of the last timed run, and their imbalance
+     * (with their random number and gaussian pair times if 'timed')
+     */
+    pub fn print_workers( &self, timed: &bool )
+    {
+        let phase = |t: f64| -> String { if *timed { format!("{:.6}", t) } else { String::from("-") } };
+        let join: f64 = self.workers.iter().map(|w| w.end).fold(0.0, f64::max);
+        let (ratio, idle): (f64, f64) = self.imbalance();
+	    println!("\n Per-thread timeline (seconds since the run started):\n");
+	    println!(" {:>6} {:>10} {:>10} {:>8} {:>10} {:>10} {:>10} {:>10}",
+	            "Thread", "Start", "End", "Batches", "Random", "Gaussian", "Busy", "Idle");
+	    for (i, w) in self.workers.iter().enumerate()
+	    {
+	        println!(" {:>6} {:>10.6} {:>10.6} {:>8} {:>10} {:>10} {:>10.6} {:>10.6}",
+	                i, w.start, w.end, w.batches, phase(w.time_random), phase(w.time_gaussian),
+	                w.busy(), join - w.end);
+	    }
+	    println!("\n Imbalance (max/mean busy) = {:.4}", ratio);
+	    println!(" Idle at the join          = {:.6} s (over all threads)", idle);
+    }
+}
+
+fn run_on<B: ParallelBackend>( backend: &B, config: &Config, M: &usize ) -> EpResult
+{
+    let (mut dum0, mut dum1, mut dum2): (f64, f64, [f64; 1]) = (1.0, 1.0, [1.0; 1]);
+    let mut x: Vec<f64> = vec![0.0; NK_PLUS];
+    let mut sums: EpSums = EpSums::default();
+    let mut seconds: Vec<f64> = Vec::with_capacity(config.repeat);
+    let mut verification: Verification = Verification::Successful;
+    let origin: Option<Instant> = if config.trace { Some(Instant::now()) } else { None };
+    let mut spans: Vec<Span> = Vec::new();
+
+    /* Call the random number generator functions and initialize
+     * the x-array to reduce the effects of paging on the timings.
+     * Also, call all mathematical functions that are used. Make
+     * sure these initializations cannot be eliminated as dead code.
+    */
+    f64rand::nrandlc(&0, &mut dum0, &dum1, &mut dum2);
+    dum2[0] = f64rand::randlc(&mut dum1, &dum0);
+    x[..NK_PLUS].fill(-1.0e99);
+    std::hint::black_box(f64::max(x[NK], dum2[0]).abs().sqrt().ln());
+    if let Some(origin) = &origin
+    {
+        spans.push(Span::new("paging warm-up", "init", &MAIN_THREAD, &0.0, &trace::since(origin)));
+    }
+
+    // discarded warm-up runs
+    for i in 0usize..config.warmup
+    {
+        let start: f64 = origin.as_ref().map_or(0.0, trace::since);
+        let mut warmup_sums: EpSums = std::hint::black_box(compute(backend, M, &false, &origin));
+        if let Some(origin) = &origin
+        {
+            spans.push(Span::new(&format!("warm-up run {}", i + 1), "run", &MAIN_THREAD, &start,
+                                 &trace::since(origin)));
+            spans.extend(warmup_sums.take_spans());
+        }
+    }
+
+    // timed runs
+    let sampler: UsageSampler = UsageSampler::start(&config.perf);
+    for i in 0usize..config.repeat.max(1)
+    {
+        let start_moment: Instant = Instant::now();
+        let start: f64 = origin.as_ref().map_or(0.0, trace::since);
+        sums = compute(backend, M, &config.use_timers, &origin);
+        seconds.push(start_moment.elapsed().as_secs_f64());
+        verification = verification.and(verify(M, &sums.sx, &sums.sy));
+        if let Some(origin) = &origin
+        {
+            spans.push(Span::new(&format!("timed run {}", i + 1), "run", &MAIN_THREAD, &start,
+                                 &trace::since(origin)));
+            spans.extend(sums.take_spans());
+        }
+    }
+
+    let usage: ResourceUsage = sampler.stop();
+    let threads: usize = backend.num_threads();
+    let operations: f64 = f64::powf(2.0, (M + 1) as f64); // 2^(M+1) random numbers
+    let time: f64 = stats::summarize(&seconds).median;
+    let workers: f64 = sums.workers.len().max(1) as f64;
+    return EpResult
+    {
+        class:          config.class,
+        M:              *M,
+        backend:        backend.name(),
+        threads,
+        binding:        backend.binding(),
+        sx:             sums.sx,
+        sy:             sums.sy,
+        q:              sums.q,
+        gaussian_pairs: sums.q.iter().sum(),
+        mops:           seconds.iter().map(|t| (operations / t) / 1e6).collect(),
+        seconds,
+        time,
+        Mops:           (operations / time) / 1e6,
+        time_gaussian:  sums.workers.iter().map(|w| w.time_gaussian).sum::<f64>() / workers,
+        time_random:    sums.workers.iter().map(|w| w.time_random).sum::<f64>() / workers,
+        workers:        sums.workers,
+        trace:          spans,
+        verification,
+        usage,
+    };
+}
+
+/* runs EP as 'config' says, without printing anything; an error if its class
+ * is unknown, or its backend unavailable
+ */
+pub fn run( config: &Config ) -> Result<EpResult, String>
+{
+    let M: usize = EpClass::of(config)?.M;
+
+    return match config.backend
+    {
+        BackendKind::Serial => Ok(run_on(&Serial, config, &M)),
+        BackendKind::ThreadsChannels =>
+            Ok(run_on(&ThreadsChannels::with_binding(&config.backend.threads(&config.threads),
+                                                     &config.binding), config, &M)),
+        #[cfg(feature = "rayon")]
+        BackendKind::Rayon => Ok(run_on(&Rayon::with_binding(&config.threads, &config.binding),
+                                        config, &M)),
+        #[cfg(not(feature = "rayon"))]
+        BackendKind::Rayon => Err(String::from("the Rayon backend needs the \"rayon\" feature")),
+    };
+}
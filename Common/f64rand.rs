@@ -1,7 +1,7 @@
 ///////////////////////////////////////////////////////////////////////////////
 //                                                                           //
 //                                 Rusty NPB                                 //
-//                              (Rayon Version)                              //
+//                               (Common Code)                               //
 //                                                                           //
 //                                MIT License                                //
 //                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
@@ -69,20 +69,20 @@ pub fn randlc( x: &mut f64, a: &f64 ) -> f64
 
     // breaking A into two parts such that A = 2^23 * A1 + A2
     t1 = r23 * a;
-    a1 = t1.round();
+    a1 = t1.trunc();
     a2 = a - t23 * a1;
 
     // breaking X into two parts such that X = 2^23 * X1 + X2
     t1 = r23 * *x;
-    x1 = t1.round();
+    x1 = t1.trunc();
     x2 = *x - t23 * x1;
     t1 = a1 * x2 + a2 * x1;
-    t2 = (r23 * t1).round();
+    t2 = (r23 * t1).trunc();
 
     // computing Z = A1 * X2 + A2 * X1  (mod 2^23)
     z = t1 - t23 * t2;
     t3 = t23 * z + a2 * x2;
-    t4 = (r46 * t3).round();
+    t4 = (r46 * t3).trunc();
 
     // computing X = 2^23 * Z + A2 * X2  (mod 2^46)
     *x = t3 - (t23 * t23) * t4;
@@ -120,28 +120,28 @@ pub fn nrandlc( n: &usize, x: &mut f64, a: &f64, y: &mut [f64] )
 
     // breaking A into two parts such that A = 2^23 * A1 + A2
     t1 = r23 * a;
-    a1 = t1.round();
+    a1 = t1.trunc();
     a2 = a - t23 * a1;
 
     // generating N results
-    for i in 1usize..=*n
+    for y_i in y.iter_mut().take(*n)
     {
         // breaking X into two parts such that X = 2^23 * X1 + X2
         t1 = r23 * x_copy;
-        x1 = t1.round();
+        x1 = t1.trunc();
         x2 = x_copy - t23 * x1;
         t1 = a1 * x2 + a2 * x1;
-        t2 = (r23 * t1).round();
+        t2 = (r23 * t1).trunc();
 
         // computing Z = A1 * X2 + A2 * X1  (mod 2^23)
         z = t1 - t23 * t2;
         t3 = t23 * z + a2 * x2;
-        t4 = (r46 * t3).round();
+        t4 = (r46 * t3).trunc();
 
         // computing X = 2^23 * Z + A2 * X2  (mod 2^46)
         x_copy = t3 - t46 * t4;
 
-        y[i] = r46 * x_copy;
+        *y_i = r46 * x_copy;
     }
 
     *x = x_copy;
@@ -0,0 +1,217 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    The machine a benchmark ran on (CPU, topology, caches, memory, kernel  //
+//    and the CPUs the process may run on), read from /proc and /sys on Linux,//
+//    so results collected across clusters can be told apart.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+use std::fs;
+
+
+
+pub struct HostInfo
+{
+    pub cpu_model:    String,
+    pub sockets:      usize,
+    pub cores:        usize,       // physical cores, over all sockets
+    pub threads:      usize,       // logical CPUs
+    pub caches:       Vec<String>, // "L1d 48K", "L2 2048K", ... (of CPU 0)
+    pub numa_nodes:   usize,
+    pub memory_kb:    u64,         // total memory
+    pub kernel:       String,
+    pub governor:     String,      // of CPU 0's frequency scaling
+    pub allowed_cpus: String,      // as a list ("0-3,8")
+}
+
+// the trimmed contents of 'path', or None if it cannot be read
+fn read( path: &str ) -> Option<String>
+{
+    return fs::read_to_string(path).ok().map(|contents| contents.trim().to_string());
+}
+
+// the value of 'field' of /proc/meminfo ("MemTotal", "MemAvailable", ...), in kilobytes
+pub fn meminfo( field: &str ) -> Option<u64>
+{
+    return read("/proc/meminfo")?.lines()
+            .find(|line| line.split(':').next() == Some(field))
+            .and_then(|line| line.split_whitespace().nth(1))
+            .and_then(|kb| kb.parse::<u64>().ok());
+}
+
+// the numbers of the entries of 'dir' named 'prefix' followed by a number
+fn numbered_entries( dir: &str, prefix: &str ) -> Vec<usize>
+{
+    let mut numbers: Vec<usize> = match fs::read_dir(dir)
+    {
+        Ok(entries) => entries.filter_map(|entry| entry.ok())
+                              .filter_map(|entry| entry.file_name().to_str()
+                                                       .and_then(|name| name.strip_prefix(prefix))
+                                                       .and_then(|n| n.parse::<usize>().ok()))
+                              .collect(),
+        Err(_) => Vec::new(),
+    };
+    numbers.sort_unstable();
+    return numbers;
+}
+
+// 'cpus' (sorted) as a list of ranges, as in "0-3,8"
+fn cpu_list( cpus: &[usize] ) -> String
+{
+    let mut ranges: Vec<String> = Vec::new();
+    let mut i: usize = 0;
+    while i < cpus.len()
+    {
+        let mut j: usize = i;
+        while ((j + 1) < cpus.len()) && (cpus[j + 1] == (cpus[j] + 1)) { j += 1; }
+        ranges.push(if i == j { cpus[i].to_string() } else { format!("{}-{}", cpus[i], cpus[j]) });
+        i = j + 1;
+    }
+    return ranges.join(",");
+}
+
+// the CPUs this process may run on (through sched_getaffinity)
+#[cfg(target_os = "linux")]
+pub fn allowed_cpus() -> Vec<usize>
+{
+    // SAFETY: 'set' is a properly sized cpu_set_t, only read after the call
+    unsafe
+    {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        if libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut set) != 0
+        {
+            return Vec::new();
+        }
+        return (0usize..(libc::CPU_SETSIZE as usize)).filter(|cpu| libc::CPU_ISSET(*cpu, &set))
+                                                      .collect();
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn allowed_cpus() -> Vec<usize>
+{
+    return Vec::new();
+}
+
+impl HostInfo
+{
+    pub fn collect() -> HostInfo
+    {
+        const CPU_DIR: &str = "/sys/devices/system/cpu";
+
+        let cpuinfo: String = read("/proc/cpuinfo").unwrap_or_default();
+        let cpu_model: String = cpuinfo.lines()
+                .find(|line| line.starts_with("model name") || line.starts_with("Model"))
+                .and_then(|line| line.split(':').nth(1))
+                .map_or(String::from("unknown"), |model| model.trim().to_string());
+
+        // sockets and cores, from the topology of every logical CPU
+        let cpus: Vec<usize> = numbered_entries(CPU_DIR, "cpu");
+        let mut packages: Vec<String> = Vec::new();
+        let mut cores: Vec<(String, String)> = Vec::new();
+        for cpu in cpus.iter()
+        {
+            let topology: String = format!("{}/cpu{}/topology", CPU_DIR, cpu);
+            let package: String = read(&format!("{}/physical_package_id", topology)).unwrap_or_default();
+            let core: String = read(&format!("{}/core_id", topology)).unwrap_or_default();
+            if !packages.contains(&package) { packages.push(package.clone()); }
+            if !cores.contains(&(package.clone(), core.clone())) { cores.push((package, core)); }
+        }
+
+        let caches: Vec<String> = numbered_entries(&format!("{}/cpu0/cache", CPU_DIR), "index")
+                .iter().filter_map(|index|
+                {
+                    let dir: String = format!("{}/cpu0/cache/index{}", CPU_DIR, index);
+                    let level: String = read(&format!("{}/level", dir))?;
+                    let kind: String = match read(&format!("{}/type", dir))?.as_str()
+                    {
+                        "Data" => String::from("d"),
+                        "Instruction" => String::from("i"),
+                        _ => String::new(),
+                    };
+                    Some(format!("L{}{} {}", level, kind, read(&format!("{}/size", dir))?))
+                }).collect();
+
+        let memory_kb: u64 = meminfo("MemTotal").unwrap_or(0);
+
+        return HostInfo
+        {
+            cpu_model,
+            sockets:      packages.len(),
+            cores:        cores.len(),
+            threads:      cpus.len(),
+            caches,
+            numa_nodes:   numbered_entries("/sys/devices/system/node", "node").len(),
+            memory_kb,
+            kernel:       read("/proc/sys/kernel/osrelease").unwrap_or(String::from("unknown")),
+            governor:     read(&format!("{}/cpu0/cpufreq/scaling_governor", CPU_DIR))
+                                  .unwrap_or(String::from("unknown")),
+            allowed_cpus: cpu_list(&allowed_cpus()),
+        };
+    }
+
+    // threads per core (simultaneous multithreading)
+    pub fn smt( &self ) -> usize
+    {
+        return self.threads.checked_div(self.cores).unwrap_or(1);
+    }
+
+    // the host lines of the report (see report::BenchmarkReport)
+    pub fn render( &self ) -> String
+    {
+        let mut out: String = String::new();
+	    out += "\n Host:\n";
+	    out += &format!("    CPU          = {:>30}\n", self.cpu_model);
+	    out += &format!("    TOPOLOGY     = {:>30}\n", format!("{} sockets, {} cores, {} threads/core",
+	                                                            self.sockets, self.cores, self.smt()));
+	    out += &format!("    CACHES       = {:>30}\n", self.caches.join(", "));
+	    out += &format!("    NUMA NODES   = {:>30}\n", self.numa_nodes);
+	    out += &format!("    MEMORY       = {:>30}\n", format!("{:.1} GiB",
+	                                                            (self.memory_kb as f64) / 1048576.0));
+	    out += &format!("    KERNEL       = {:>30}\n", self.kernel);
+	    out += &format!("    GOVERNOR     = {:>30}\n", self.governor);
+	    out += &format!("    ALLOWED CPUS = {:>30}\n", self.allowed_cpus);
+        return out;
+    }
+
+    // as a JSON object
+    pub fn to_json( &self ) -> String
+    {
+        let caches: Vec<String> = self.caches.iter().map(|cache| format!("\"{}\"", cache)).collect();
+        return format!("{{ \"cpu_model\": \"{}\", \"sockets\": {}, \"cores\": {}, \"threads\": {}, \
+                        \"smt\": {}, \"caches\": [{}], \"numa_nodes\": {}, \"memory_kb\": {}, \
+                        \"kernel\": \"{}\", \"governor\": \"{}\", \"allowed_cpus\": \"{}\" }}",
+                       self.cpu_model.replace('\\', "\\\\").replace('"', "\\\""),
+                       self.sockets, self.cores, self.threads, self.smt(), caches.join(", "),
+                       self.numa_nodes, self.memory_kb, self.kernel, self.governor,
+                       self.allowed_cpus);
+    }
+}
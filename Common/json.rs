@@ -0,0 +1,233 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    A minimal JSON reader, enough to load back the results written with    //
+//    "--json" (numbers are read as finite f64s, and \u escapes outside      //
+//    the basic multilingual plane are not supported).                       //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value
+{
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value
+{
+    // the member 'key' of an object (None for anything else)
+    pub fn get( &self, key: &str ) -> Option<&Value>
+    {
+        return match self
+        {
+            Value::Object(members) => members.iter().find(|m| m.0 == key).map(|m| &m.1),
+            _ => None,
+        };
+    }
+
+    pub fn as_f64( &self ) -> Option<f64>
+    {
+        return match self { Value::Number(n) => Some(*n), _ => None };
+    }
+
+    pub fn as_str( &self ) -> Option<&str>
+    {
+        return match self { Value::String(s) => Some(s.as_str()), _ => None };
+    }
+
+    pub fn as_bool( &self ) -> Option<bool>
+    {
+        return match self { Value::Bool(b) => Some(*b), _ => None };
+    }
+}
+
+
+
+struct Parser<'a>
+{
+    bytes: &'a [u8],
+    pos:   usize,
+}
+
+impl Parser<'_>
+{
+    fn error( &self, what: &str ) -> String
+    {
+        return format!("invalid JSON at byte {}: {}", self.pos, what);
+    }
+
+    fn skip_whitespace( &mut self )
+    {
+        while (self.pos < self.bytes.len()) && self.bytes[self.pos].is_ascii_whitespace()
+        {
+            self.pos += 1;
+        }
+    }
+
+    // skips whitespace, then consumes 'byte' if it comes next
+    fn eat( &mut self, byte: &u8 ) -> bool
+    {
+        self.skip_whitespace();
+        if self.bytes.get(self.pos) == Some(byte) { self.pos += 1; return true; }
+        return false;
+    }
+
+    fn expect( &mut self, byte: &u8 ) -> Result<(), String>
+    {
+        if self.eat(byte) { return Ok(()); }
+        return Err(self.error(&format!("expected '{}'", *byte as char)));
+    }
+
+    fn literal( &mut self, word: &str, value: Value ) -> Result<Value, String>
+    {
+        if self.bytes[self.pos..].starts_with(word.as_bytes())
+        {
+            self.pos += word.len();
+            return Ok(value);
+        }
+        return Err(self.error("unexpected character"));
+    }
+
+    fn number( &mut self ) -> Result<Value, String>
+    {
+        let start: usize = self.pos;
+        while (self.pos < self.bytes.len()) &&
+              matches!(self.bytes[self.pos], b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E')
+        {
+            self.pos += 1;
+        }
+        let text: &str = std::str::from_utf8(&self.bytes[start..self.pos]).unwrap();
+        return match text.parse::<f64>()
+        {
+            Ok(n) if n.is_finite() => Ok(Value::Number(n)),
+            Ok(_) => Err(self.error("number out of range")),
+            Err(_) => Err(self.error("invalid number")),
+        };
+    }
+
+    fn string( &mut self ) -> Result<String, String>
+    {
+        self.expect(&b'"')?;
+        let mut bytes: Vec<u8> = Vec::new();
+        loop
+        {
+            let byte: u8 = *self.bytes.get(self.pos).ok_or_else(|| self.error("unterminated string"))?;
+            self.pos += 1;
+            match byte
+            {
+                b'"' => break,
+                b'\\' =>
+                {
+                    let escape: u8 = *self.bytes.get(self.pos).ok_or_else(|| self.error("unterminated string"))?;
+                    self.pos += 1;
+                    let decoded: char = match escape
+                    {
+                        b'"' => '"', b'\\' => '\\', b'/' => '/',
+                        b'b' => '\u{8}', b'f' => '\u{c}', b'n' => '\n', b'r' => '\r', b't' => '\t',
+                        b'u' =>
+                        {
+                            let hex: &str = self.bytes.get(self.pos..(self.pos + 4))
+                                    .and_then(|h| std::str::from_utf8(h).ok())
+                                    .ok_or_else(|| self.error("invalid \\u escape"))?;
+                            self.pos += 4;
+                            u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+                                    .ok_or_else(|| self.error("invalid \\u escape"))?
+                        }
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    bytes.extend_from_slice(decoded.encode_utf8(&mut [0u8; 4]).as_bytes());
+                }
+                _ => bytes.push(byte),
+            }
+        }
+        return String::from_utf8(bytes).map_err(|_| self.error("invalid UTF-8"));
+    }
+
+    fn value( &mut self ) -> Result<Value, String>
+    {
+        self.skip_whitespace();
+        return match self.bytes.get(self.pos)
+        {
+            None => Err(self.error("unexpected end")),
+            Some(b'n') => self.literal("null", Value::Null),
+            Some(b't') => self.literal("true", Value::Bool(true)),
+            Some(b'f') => self.literal("false", Value::Bool(false)),
+            Some(b'"') => self.string().map(Value::String),
+            Some(b'[') =>
+            {
+                self.pos += 1;
+                let mut items: Vec<Value> = Vec::new();
+                if !self.eat(&b']')
+                {
+                    loop
+                    {
+                        items.push(self.value()?);
+                        if self.eat(&b']') { break; }
+                        self.expect(&b',')?;
+                    }
+                }
+                Ok(Value::Array(items))
+            }
+            Some(b'{') =>
+            {
+                self.pos += 1;
+                let mut members: Vec<(String, Value)> = Vec::new();
+                if !self.eat(&b'}')
+                {
+                    loop
+                    {
+                        self.skip_whitespace();
+                        let key: String = self.string()?;
+                        self.expect(&b':')?;
+                        members.push((key, self.value()?));
+                        if self.eat(&b'}') { break; }
+                        self.expect(&b',')?;
+                    }
+                }
+                Ok(Value::Object(members))
+            }
+            Some(_) => self.number(),
+        };
+    }
+}
+
+pub fn parse( text: &str ) -> Result<Value, String>
+{
+    let mut parser: Parser = Parser { bytes: text.as_bytes(), pos: 0 };
+    let value: Value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos != parser.bytes.len() { return Err(parser.error("trailing characters")); }
+    return Ok(value);
+}
@@ -0,0 +1,61 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    Code shared by the Serial, Rayon and TC versions of the benchmarks:    //
+//    the random number generator, the parallel backends and the kernels     //
+//    written once, generically over those backends.                         //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+// like the rest of the port, functions end with an explicit "return"
+#![allow(clippy::needless_return)]
+
+
+
+pub mod affinity;
+pub mod args;
+pub mod f64rand;
+pub mod host;
+pub mod json;
+pub mod perf;
+pub mod preflight;
+pub mod report;
+pub mod backend;
+pub mod baseline;
+pub mod build_script;
+pub mod dt;
+pub mod ep;
+pub mod lu;
+pub mod scaling;
+pub mod sp;
+pub mod stats;
+pub mod trace;
+pub mod usage;
+pub mod verification;
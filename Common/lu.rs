@@ -0,0 +1,1298 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//               Rust version of the pseudo-application "LU",                //
+//              or "lower-upper Gauss-Seidel" solver benchmark.              //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//   The original NPB 3.4.1 version was written in Fortran and belongs to:   //
+//                   http://www.nas.nasa.gov/Software/NPB/                   //
+//                     Authors of the Fortran code are:                      //
+//     S. Weeratunga, V. Venkatakrishnan, E. Barszcz, M. Yarrow, H. Jin      //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    This benchmark solves the same regular-sparse, block (5x5) system of   //
+//    nonlinear PDEs as BT and SP, but with the symmetric successive over-   //
+//    relaxation (SSOR) method: each iteration is split into a lower and an  //
+//    upper triangular system, solved plane by plane by sweeping forward and //
+//    then backward through the grid. The right hand sides, the updates and  //
+//    the jacobians of a plane are spread over the threads of the backend,   //
+//    but the sweeps run in the calling thread: every point needs the new    //
+//    values of its previous (next) neighbours in all three directions, a    //
+//    wavefront that the independent chunks and ranges of the backends       //
+//    cannot express.                                                        //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+#![allow(non_snake_case)]
+
+use std::time::Instant;
+
+use crate::affinity::Binding;
+#[cfg(feature = "rayon")]
+use crate::backend::Rayon;
+use crate::backend::{BackendKind, ParallelBackend, Serial, ThreadsChannels};
+use crate::preflight::ClassTable;
+use crate::usage::{ResourceUsage, UsageSampler};
+use crate::verification::Verification;
+
+
+
+const C1: f64 = 1.40;
+const C2: f64 = 0.40;
+const C3: f64 = 1.00e-01;
+const C4: f64 = 1.00;
+const C5: f64 = 1.40;
+
+// coefficients of the exact solution polynomial (ce(m, n) in the original)
+const CE: [[f64; 13]; 5] =
+[
+    [ 2.0, 0.0, 0.0, 4.0, 5.0, 3.0, 0.5, 0.02, 0.01, 0.03, 0.5, 0.4, 0.3 ],
+    [ 1.0, 0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 0.01, 0.03, 0.02, 0.4, 0.3, 0.5 ],
+    [ 2.0, 2.0, 0.0, 0.0, 0.0, 2.0, 3.0, 0.04, 0.03, 0.05, 0.3, 0.5, 0.4 ],
+    [ 2.0, 2.0, 0.0, 0.0, 0.0, 2.0, 3.0, 0.03, 0.05, 0.04, 0.2, 0.1, 0.3 ],
+    [ 5.0, 4.0, 3.0, 2.0, 0.1, 0.4, 0.3, 0.05, 0.04, 0.03, 0.1, 0.3, 0.2 ],
+];
+
+// timers (only used when Config::use_timers is set)
+pub const T_TOTAL: usize = 0;
+pub const T_RHS:   usize = 1;
+pub const T_JACLD: usize = 2;
+pub const T_BLTS:  usize = 3;
+pub const T_JACU:  usize = 4;
+pub const T_BUTS:  usize = 5;
+pub const T_ADD:   usize = 6;
+pub const T_LAST:  usize = 7;
+
+
+
+// constants that depend on the direction (xi, eta or zeta)
+#[derive(Clone, Copy)]
+struct Direction
+{
+    t1: f64,      // tx1, ty1 or tz1
+    t2: f64,      // tx2, ty2 or tz2
+    t3: f64,      // tx3, ty3 or tz3
+    d:  [f64; 5], // dx1..dx5, dy1..dy5 or dz1..dz5
+    h:  f64,      // dxi, deta or dzeta
+}
+
+type Block = [[f64; 5]; 5];
+
+
+
+struct LU
+{
+    n:      [usize; 3], // nx0, ny0, nz0
+    dt:     f64,
+    omega:  f64,
+    dssp:   f64,
+    dirs:   [Direction; 3],
+
+    u:      Vec<[f64; 5]>,
+    rsd:    Vec<[f64; 5]>,
+    frct:   Vec<[f64; 5]>,
+    qs:     Vec<f64>,
+    rho_i:  Vec<f64>,
+
+    // the jacobian blocks of the current plane (x, y and z neighbours, and diagonal)
+    jac:    Vec<[Block; 4]>,
+    tv:     Vec<[f64; 5]>,
+
+    use_timers: bool,
+    progress:   bool, // prints the time steps
+    timers:     [u128; T_LAST],
+}
+
+
+
+impl LU
+{
+    fn new( n: &[usize; 3], dt: &f64, omega: &f64, use_timers: &bool, progress: &bool ) -> LU
+    {
+        /* this is "setcoeff" from the original code */
+
+        let direction = |points: usize, d: f64| -> Direction
+        {
+            let h: f64 = 1.0 / ((points - 1) as f64);
+            Direction
+            {
+                t1: 1.0 / (h * h),
+                t2: 1.0 / (2.0 * h),
+                t3: 1.0 / h,
+                d:  [d; 5],
+                h,
+            }
+        };
+
+        // diffusion coefficients
+        let dirs: [Direction; 3] = [direction(n[0], 0.75), direction(n[1], 0.75),
+                                    direction(n[2], 1.00)];
+
+        let points: usize = n[0] * n[1] * n[2];
+        let plane: usize = n[0] * n[1];
+
+        return LU
+        {
+            n: *n,
+            dt: *dt,
+            omega: *omega,
+
+            // fourth difference dissipation
+            dssp: f64::max(dirs[0].d[0], f64::max(dirs[1].d[0], dirs[2].d[0])) / 4.0,
+            dirs,
+
+            u:     vec![[0.0; 5]; points],
+            rsd:   vec![[0.0; 5]; points],
+            frct:  vec![[0.0; 5]; points],
+            qs:    vec![0.0; points],
+            rho_i: vec![0.0; points],
+
+            jac:   vec![[[[0.0; 5]; 5]; 4]; plane],
+            tv:    vec![[0.0; 5]; plane],
+
+            use_timers: *use_timers,
+            progress: *progress,
+            timers: [0; T_LAST],
+        };
+    }
+
+    // index of the grid point (i, j, k)
+    fn at( &self, i: &usize, j: &usize, k: &usize ) -> usize
+    {
+        return ((k * self.n[1]) + j) * self.n[0] + i;
+    }
+
+    // index of the point (i, j) in the per-plane arrays
+    fn in_plane( &self, i: &usize, j: &usize ) -> usize
+    {
+        return (j * self.n[0]) + i;
+    }
+
+    fn start_timer( &self ) -> Instant
+    {
+        return Instant::now();
+    }
+
+    fn stop_timer( &mut self, timer: &usize, moment: &Instant )
+    {
+        if self.use_timers { self.timers[*timer] += moment.elapsed().as_micros(); }
+    }
+
+	/* ********************************************************************* */
+
+    // the exact solution at the grid point (i, j, k)
+    fn exact( &self, i: &usize, j: &usize, k: &usize, u000ijk: &mut [f64; 5] )
+    {
+        let xi: f64   = (*i as f64) / ((self.n[0] - 1) as f64);
+        let eta: f64  = (*j as f64) / ((self.n[1] - 1) as f64);
+        let zeta: f64 = (*k as f64) / ((self.n[2] - 1) as f64);
+
+        for m in 0usize..5
+        {
+            u000ijk[m] = CE[m][0] +
+                         (CE[m][1] + (CE[m][4] + (CE[m][7] + CE[m][10] * xi) * xi) * xi) * xi +
+                         (CE[m][2] + (CE[m][5] + (CE[m][8] + CE[m][11] * eta) * eta) * eta) * eta +
+                         (CE[m][3] + (CE[m][6] + (CE[m][9] + CE[m][12] * zeta) * zeta) * zeta) * zeta;
+        }
+    }
+
+    // fourth order dissipation at position 'p' of a line of 'n' values, v(q) being the q-th
+    fn dissipation<V: Fn(usize) -> f64>( p: &usize, n: &usize, v: V ) -> f64
+    {
+        let p: usize = *p;
+        return if p == 1 { 5.0 * v(p) - 4.0 * v(p + 1) + v(p + 2) }
+               else if p == 2 { -4.0 * v(p - 1) + 6.0 * v(p) - 4.0 * v(p + 1) + v(p + 2) }
+               else if p == (n - 3) { v(p - 2) - 4.0 * v(p - 1) + 6.0 * v(p) - 4.0 * v(p + 1) }
+               else if p == (n - 2) { v(p - 2) - 4.0 * v(p - 1) + 5.0 * v(p) }
+               else { v(p - 2) - 4.0 * v(p - 1) + 6.0 * v(p) - 4.0 * v(p + 1) + v(p + 2) };
+    }
+
+    /* calls 'f' with the index and the value in 'field' (one per grid point)
+     * of every interior point, the planes being spread over the threads of
+     * 'backend'
+     */
+    fn for_interior<B, T, F>( n: &[usize; 3], backend: &B, field: &mut [T], f: F )
+        where B: ParallelBackend, T: Send, F: Fn(usize, &mut T) + Sync
+    {
+        let [nx, ny, nz] = *n;
+        let plane: usize = nx * ny;
+
+        backend.parallel_for_chunks(field, &plane, |k, values|
+        {
+            if (k == 0) || (k == (nz - 1)) { return; }
+            for j in 1usize..(ny - 1)
+            {
+                for i in 1usize..(nx - 1)
+                {
+                    let q: usize = (j * nx) + i;
+                    f((k * plane) + q, &mut values[q]);
+                }
+            }
+        });
+    }
+
+    // set the boundary values of dependent variables
+    fn setbv( &mut self )
+    {
+        let [nx, ny, nz] = self.n;
+        let mut temp: [f64; 5] = [0.0; 5];
+
+        // set the dependent variable values along the top and bottom faces
+        for j in 0usize..ny
+        {
+            for i in 0usize..nx
+            {
+                for k in [0, nz - 1]
+                {
+                    self.exact(&i, &j, &k, &mut temp);
+                    let point: usize = self.at(&i, &j, &k);
+                    self.u[point] = temp;
+                }
+            }
+        }
+
+        // set the dependent variable values along north and south faces
+        for k in 0usize..nz
+        {
+            for i in 0usize..nx
+            {
+                for j in [0, ny - 1]
+                {
+                    self.exact(&i, &j, &k, &mut temp);
+                    let point: usize = self.at(&i, &j, &k);
+                    self.u[point] = temp;
+                }
+            }
+        }
+
+        // set the dependent variable values along east and west faces
+        for k in 0usize..nz
+        {
+            for j in 0usize..ny
+            {
+                for i in [0, nx - 1]
+                {
+                    self.exact(&i, &j, &k, &mut temp);
+                    let point: usize = self.at(&i, &j, &k);
+                    self.u[point] = temp;
+                }
+            }
+        }
+    }
+
+    /* set the initial values of independent variables based on tri-linear
+     * interpolation of boundary values in the computational space
+     */
+    fn setiv( &mut self )
+    {
+        let [nx, ny, nz] = self.n;
+        let (mut ue_1jk, mut ue_nx0jk): ([f64; 5], [f64; 5]) = ([0.0; 5], [0.0; 5]);
+        let (mut ue_i1k, mut ue_iny0k): ([f64; 5], [f64; 5]) = ([0.0; 5], [0.0; 5]);
+        let (mut ue_ij1, mut ue_ijnz): ([f64; 5], [f64; 5]) = ([0.0; 5], [0.0; 5]);
+
+        for k in 1usize..(nz - 1)
+        {
+            let zeta: f64 = (k as f64) / ((nz - 1) as f64);
+            for j in 1usize..(ny - 1)
+            {
+                let eta: f64 = (j as f64) / ((ny - 1) as f64);
+                for i in 1usize..(nx - 1)
+                {
+                    let xi: f64 = (i as f64) / ((nx - 1) as f64);
+                    self.exact(&0, &j, &k, &mut ue_1jk);
+                    self.exact(&(nx - 1), &j, &k, &mut ue_nx0jk);
+                    self.exact(&i, &0, &k, &mut ue_i1k);
+                    self.exact(&i, &(ny - 1), &k, &mut ue_iny0k);
+                    self.exact(&i, &j, &0, &mut ue_ij1);
+                    self.exact(&i, &j, &(nz - 1), &mut ue_ijnz);
+
+                    let point: usize = self.at(&i, &j, &k);
+                    for m in 0usize..5
+                    {
+                        let pxi: f64   = (1.0 - xi) * ue_1jk[m] + xi * ue_nx0jk[m];
+                        let peta: f64  = (1.0 - eta) * ue_i1k[m] + eta * ue_iny0k[m];
+                        let pzeta: f64 = (1.0 - zeta) * ue_ij1[m] + zeta * ue_ijnz[m];
+
+                        self.u[point][m] = pxi + peta + pzeta -
+                                           pxi * peta - peta * pzeta - pzeta * pxi +
+                                           pxi * peta * pzeta;
+                    }
+                }
+            }
+        }
+    }
+
+    // the convective flux along direction 'd' of the values 'w' of a point
+    fn convective_flux( d: &usize, w: &[f64; 5] ) -> [f64; 5]
+    {
+        let n: usize = d + 1; // the momentum component normal to the direction
+        let un: f64 = w[n] / w[0];
+        let q: f64 = 0.50 * (w[1] * w[1] + w[2] * w[2] + w[3] * w[3]) / w[0];
+        let mut flux: [f64; 5] = [0.0; 5];
+
+        flux[0] = w[n];
+        for m in 1usize..4 { flux[m] = w[m] * un; }
+        flux[n] = w[n] * un + C2 * (w[4] - q);
+        flux[4] = (C1 * w[4] - C2 * q) * un;
+        return flux;
+    }
+
+    /* the viscous flux along direction 'd' between the values 'wm1' of a
+     * point and the values 'w' of the next one (but its first component)
+     */
+    fn viscous_flux( dir: &Direction, d: &usize, w: &[f64; 5], wm1: &[f64; 5] ) -> [f64; 5]
+    {
+        let n: usize = d + 1;
+        let mut flux: [f64; 5] = [0.0; 5];
+
+        let tmp: f64 = 1.0 / w[0];
+        let (u21, u31, u41, u51): (f64, f64, f64, f64) =
+                (tmp * w[1], tmp * w[2], tmp * w[3], tmp * w[4]);
+
+        let tmp: f64 = 1.0 / wm1[0];
+        let (u21m1, u31m1, u41m1, u51m1): (f64, f64, f64, f64) =
+                (tmp * wm1[1], tmp * wm1[2], tmp * wm1[3], tmp * wm1[4]);
+
+        let (un, unm1): (f64, f64) = ([u21, u31, u41][*d], [u21m1, u31m1, u41m1][*d]);
+
+        flux[1] = dir.t3 * (u21 - u21m1);
+        flux[2] = dir.t3 * (u31 - u31m1);
+        flux[3] = dir.t3 * (u41 - u41m1);
+        flux[n] = (4.0 / 3.0) * dir.t3 * (un - unm1);
+        flux[4] = 0.50 * (1.0 - C1 * C5) * dir.t3 *
+                  ((u21 * u21 + u31 * u31 + u41 * u41) -
+                   (u21m1 * u21m1 + u31m1 * u31m1 + u41m1 * u41m1)) +
+                  (1.0 / 6.0) * dir.t3 * (un * un - unm1 * unm1) +
+                  C1 * C5 * dir.t3 * (u51 - u51m1);
+        return flux;
+    }
+
+    /* computes the flux differences of 'v' at every interior point, adding
+     * them to 'out'; this is shared by erhs (where v is the exact solution and
+     * out is the forcing term) and rhs (where v is u and out is the residual)
+     */
+    fn flux_differences<B: ParallelBackend>( &self, backend: &B, v: &[[f64; 5]],
+                                             out: &mut [[f64; 5]] )
+    {
+        let dssp: f64 = self.dssp;
+        let [nx, ny, _] = self.n;
+        let strides: [usize; 3] = [1, nx, nx * ny];
+
+        LU::for_interior(&self.n, backend, out, |point, o|
+        {
+            let ijk: [usize; 3] = [point % nx, (point / nx) % ny, point / (nx * ny)];
+
+            for d in 0usize..3
+            {
+                let dir: Direction = self.dirs[d];
+                let (stride, len, p): (usize, usize, usize) = (strides[d], self.n[d], ijk[d]);
+                let (pm, pp): (usize, usize) = (point - stride, point + stride);
+                let w: [f64; 5] = v[point];
+
+                let (flux_m, flux_p): ([f64; 5], [f64; 5]) =
+                        (LU::convective_flux(&d, &v[pm]), LU::convective_flux(&d, &v[pp]));
+                for m in 0usize..5 { o[m] -= dir.t2 * (flux_p[m] - flux_m[m]); }
+
+                let (flux, flux_p): ([f64; 5], [f64; 5]) = (LU::viscous_flux(&dir, &d, &w, &v[pm]),
+                                                            LU::viscous_flux(&dir, &d, &v[pp], &w));
+                o[0] += dir.d[0] * dir.t1 * (v[pm][0] - 2.0 * w[0] + v[pp][0]);
+                for m in 1usize..5
+                {
+                    o[m] = o[m] + dir.t3 * C3 * C4 * (flux_p[m] - flux[m]) +
+                           dir.d[m] * dir.t1 * (v[pm][m] - 2.0 * w[m] + v[pp][m]);
+                }
+
+                // fourth-order dissipation
+                let first: usize = point - (p * stride);
+                for m in 0usize..5
+                {
+                    o[m] -= dssp * LU::dissipation(&p, &len, |q| v[first + (q * stride)][m]);
+                }
+            }
+        });
+    }
+
+    // compute the right hand side based on exact solution
+    fn erhs<B: ParallelBackend>( &mut self, backend: &B )
+    {
+        let [nx, ny, nz] = self.n;
+        // the exact solution goes in 'rsd', which the first rhs overwrites anyway
+        let mut exact: Vec<[f64; 5]> = std::mem::take(&mut self.rsd);
+        let mut frct: Vec<[f64; 5]> = std::mem::take(&mut self.frct);
+        frct.fill([0.0; 5]);
+
+        for k in 0usize..nz
+        {
+            for j in 0usize..ny
+            {
+                for i in 0usize..nx
+                {
+                    let point: usize = self.at(&i, &j, &k);
+                    self.exact(&i, &j, &k, &mut exact[point]);
+                }
+            }
+        }
+
+        // xi-, eta- and zeta-direction flux differences
+        self.flux_differences(backend, &exact, &mut frct);
+        self.frct = frct;
+        self.rsd = exact;
+    }
+
+    // compute the right hand sides
+    fn rhs<B: ParallelBackend>( &mut self, backend: &B )
+    {
+        let moment: Instant = self.start_timer();
+
+        for point in 0usize..self.u.len()
+        {
+            let u: [f64; 5] = self.u[point];
+            for m in 0usize..5 { self.rsd[point][m] = -self.frct[point][m]; }
+            let tmp: f64 = 1.0 / u[0];
+            self.rho_i[point] = tmp;
+            self.qs[point] = 0.50 * (u[1] * u[1] + u[2] * u[2] + u[3] * u[3]) * tmp;
+        }
+
+        // xi-, eta- and zeta-direction flux differences
+        let mut rsd: Vec<[f64; 5]> = std::mem::take(&mut self.rsd);
+        self.flux_differences(backend, &self.u, &mut rsd);
+        self.rsd = rsd;
+
+        self.stop_timer(&T_RHS, &moment);
+    }
+
+	/* ********************************************************************* */
+
+    // the diagonal block of the jacobian at 'point'
+    fn diagonal_block( &self, point: &usize ) -> Block
+    {
+        let (dt, r43, c1345, c34): (f64, f64, f64, f64) = (self.dt, 4.0 / 3.0, C1 * C3 * C4 * C5, C3 * C4);
+        let [dx, dy, dz] = self.dirs;
+        let (tx1, ty1, tz1): (f64, f64, f64) = (dx.t1, dy.t1, dz.t1);
+        let u: [f64; 5] = self.u[*point];
+        let tmp1: f64 = self.rho_i[*point];
+        let tmp2: f64 = tmp1 * tmp1;
+        let tmp3: f64 = tmp1 * tmp2;
+        let mut d: Block = [[0.0; 5]; 5];
+
+        // (t?1 * r43) on the direction of the momentum component, t?1 elsewhere
+        let visc: [f64; 4] = [0.0, tx1 * r43 + ty1 + tz1, tx1 + ty1 * r43 + tz1, tx1 + ty1 + tz1 * r43];
+        let energy: [f64; 4] =
+        [
+            0.0,
+            tx1 * (r43 * c34 - c1345) + ty1 * (c34 - c1345) + tz1 * (c34 - c1345),
+            tx1 * (c34 - c1345) + ty1 * (r43 * c34 - c1345) + tz1 * (c34 - c1345),
+            tx1 * (c34 - c1345) + ty1 * (c34 - c1345) + tz1 * (r43 * c34 - c1345),
+        ];
+
+        d[0][0] = 1.0 + dt * 2.0 * (tx1 * dx.d[0] + ty1 * dy.d[0] + tz1 * dz.d[0]);
+        for m in 1usize..4
+        {
+            d[m][0] = -dt * 2.0 * visc[m] * c34 * tmp2 * u[m];
+            d[m][m] = 1.0 + dt * 2.0 * c34 * tmp1 * visc[m] +
+                      dt * 2.0 * (tx1 * dx.d[m] + ty1 * dy.d[m] + tz1 * dz.d[m]);
+            d[4][m] = dt * 2.0 * tmp2 * u[m] * energy[m];
+        }
+        d[4][0] = -dt * 2.0 * ((energy[1] * (u[1] * u[1]) + energy[2] * (u[2] * u[2]) +
+                                energy[3] * (u[3] * u[3])) * tmp3 +
+                               (tx1 + ty1 + tz1) * c1345 * tmp2 * u[4]);
+        d[4][4] = 1.0 + dt * 2.0 * (tx1 + ty1 + tz1) * c1345 * tmp1 +
+                  dt * 2.0 * (tx1 * dx.d[4] + ty1 * dy.d[4] + tz1 * dz.d[4]);
+
+        return d;
+    }
+
+    /* the off-diagonal block of the jacobian coupling a point to its
+     * neighbour 'point' along direction 'd'; 'sign' is -1 for the lower
+     * (previous) neighbours, and +1 for the upper (next) ones
+     */
+    fn neighbour_block( &self, d: &usize, sign: &f64, point: &usize ) -> Block
+    {
+        let (r43, c1345, c34): (f64, f64, f64) = (4.0 / 3.0, C1 * C3 * C4 * C5, C3 * C4);
+        let dir: Direction = self.dirs[*d];
+        let (dtt1, dtt2): (f64, f64) = (self.dt * dir.t1, sign * self.dt * dir.t2);
+        let n: usize = d + 1; // the momentum component normal to the direction
+        let u: [f64; 5] = self.u[*point];
+        let qs: f64 = self.qs[*point];
+        let tmp1: f64 = self.rho_i[*point];
+        let tmp2: f64 = tmp1 * tmp1;
+        let tmp3: f64 = tmp1 * tmp2;
+        let mut a: Block = [[0.0; 5]; 5];
+
+        a[0][0] = -dtt1 * dir.d[0];
+        a[0][n] = dtt2;
+
+        for m in 1usize..4
+        {
+            if m == n
+            {
+                a[m][0] = dtt2 * (-(u[n] * tmp1) * (u[n] * tmp1) + C2 * (qs * tmp1)) -
+                          dtt1 * (-r43 * c34 * tmp2 * u[n]);
+                for c in 1usize..4 { a[m][c] = dtt2 * (-C2 * (u[c] * tmp1)); }
+                a[m][m] = dtt2 * ((2.0 - C2) * (u[n] * tmp1)) - dtt1 * (r43 * c34 * tmp1) -
+                          dtt1 * dir.d[m];
+                a[m][4] = dtt2 * C2;
+            }
+            else
+            {
+                a[m][0] = dtt2 * (-(u[m] * u[n]) * tmp2) - dtt1 * (-c34 * tmp2 * u[m]);
+                a[m][n] = dtt2 * (u[m] * tmp1);
+                a[m][m] = dtt2 * (u[n] * tmp1) - dtt1 * (c34 * tmp1) - dtt1 * dir.d[m];
+            }
+        }
+
+        // c34 - c1345, or (r43 * c34 - c1345) for the normal component
+        let mut coeff: [f64; 4] = [0.0, c34 - c1345, c34 - c1345, c34 - c1345];
+        coeff[n] = r43 * c34 - c1345;
+
+        a[4][0] = dtt2 * ((C2 * 2.0 * qs - C1 * u[4]) * (u[n] * tmp2)) -
+                  dtt1 * (-coeff[1] * tmp3 * (u[1] * u[1]) - coeff[2] * tmp3 * (u[2] * u[2]) -
+                          coeff[3] * tmp3 * (u[3] * u[3]) - c1345 * tmp2 * u[4]);
+        for c in 1usize..4
+        {
+            a[4][c] = if c == n
+                      {
+                          dtt2 * (C1 * (u[4] * tmp1) - C2 * (qs * tmp1 + u[n] * u[n] * tmp2)) -
+                          dtt1 * coeff[c] * tmp2 * u[c]
+                      }
+                      else
+                      {
+                          dtt2 * (-C2 * (u[c] * u[n]) * tmp2) - dtt1 * coeff[c] * tmp2 * u[c]
+                      };
+        }
+        a[4][4] = dtt2 * (C1 * (u[n] * tmp1)) - dtt1 * c1345 * tmp1 - dtt1 * dir.d[4];
+
+        return a;
+    }
+
+    /* solves tmat * x = tv by gaussian elimination (the "diagonal block
+     * inversion"), replacing tv with the solution x
+     */
+    fn solve_block( tmat: &mut Block, tv: &mut [f64; 5] )
+    {
+        // forward elimination
+        for p in 0usize..4
+        {
+            let pivot: [f64; 5] = tmat[p];
+            let tmp1: f64 = 1.0 / pivot[p];
+            for r in (p + 1)..5
+            {
+                let tmp: f64 = tmp1 * tmat[r][p];
+                for c in (p + 1)..5 { tmat[r][c] -= tmp * pivot[c]; }
+                tv[r] -= tv[p] * tmp;
+            }
+        }
+
+        // back substitution
+        for r in (0usize..5).rev()
+        {
+            for c in (r + 1)..5 { tv[r] -= tmat[r][c] * tv[c]; }
+            tv[r] /= tmat[r][r];
+        }
+    }
+
+    // compute the lower triangular part of the jacobian matrix
+    fn jacld<B: ParallelBackend>( &mut self, backend: &B, k: &usize )
+    {
+        let moment: Instant = self.start_timer();
+        let [nx, ny, _] = self.n;
+        let mut jac: Vec<[Block; 4]> = std::mem::take(&mut self.jac);
+        let lu: &LU = self;
+
+        // the blocks of a point only depend on its neighbours' u, so the rows are spread
+        backend.parallel_for_chunks(&mut jac, &nx, |j, row|
+        {
+            if (j == 0) || (j == (ny - 1)) { return; }
+            for (i, blocks) in row.iter_mut().enumerate().take(nx - 1).skip(1)
+            {
+                *blocks = [lu.neighbour_block(&0, &-1.0, &lu.at(&(i - 1), &j, k)),
+                           lu.neighbour_block(&1, &-1.0, &lu.at(&i, &(j - 1), k)),
+                           lu.neighbour_block(&2, &-1.0, &lu.at(&i, &j, &(k - 1))),
+                           lu.diagonal_block(&lu.at(&i, &j, k))];
+            }
+        });
+        self.jac = jac;
+
+        self.stop_timer(&T_JACLD, &moment);
+    }
+
+    // compute the upper triangular part of the jacobian matrix
+    fn jacu<B: ParallelBackend>( &mut self, backend: &B, k: &usize )
+    {
+        let moment: Instant = self.start_timer();
+        let [nx, ny, _] = self.n;
+        let mut jac: Vec<[Block; 4]> = std::mem::take(&mut self.jac);
+        let lu: &LU = self;
+
+        backend.parallel_for_chunks(&mut jac, &nx, |j, row|
+        {
+            if (j == 0) || (j == (ny - 1)) { return; }
+            for (i, blocks) in row.iter_mut().enumerate().take(nx - 1).skip(1)
+            {
+                *blocks = [lu.neighbour_block(&0, &1.0, &lu.at(&(i + 1), &j, k)),
+                           lu.neighbour_block(&1, &1.0, &lu.at(&i, &(j + 1), k)),
+                           lu.neighbour_block(&2, &1.0, &lu.at(&i, &j, &(k + 1))),
+                           lu.diagonal_block(&lu.at(&i, &j, k))];
+            }
+        });
+        self.jac = jac;
+
+        self.stop_timer(&T_JACU, &moment);
+    }
+
+    // compute the regular-sparse, block lower triangular solution
+    fn blts( &mut self, k: &usize )
+    {
+        let moment: Instant = self.start_timer();
+        let [nx, ny, _] = self.n;
+        let omega: f64 = self.omega;
+
+        for j in 1usize..(ny - 1)
+        {
+            for i in 1usize..(nx - 1)
+            {
+                let (q, point): (usize, usize) = (self.in_plane(&i, &j), self.at(&i, &j, k));
+                let below: [f64; 5] = self.rsd[self.at(&i, &j, &(k - 1))];
+                let ldz: &Block = &self.jac[q][2];
+                for (m, row) in ldz.iter().enumerate()
+                {
+                    self.rsd[point][m] -= omega * (row[0] * below[0] + row[1] * below[1] +
+                                                   row[2] * below[2] + row[3] * below[3] +
+                                                   row[4] * below[4]);
+                }
+            }
+        }
+
+        for j in 1usize..(ny - 1)
+        {
+            for i in 1usize..(nx - 1)
+            {
+                let (q, point): (usize, usize) = (self.in_plane(&i, &j), self.at(&i, &j, k));
+                let south: [f64; 5] = self.rsd[self.at(&i, &(j - 1), k)];
+                let west: [f64; 5] = self.rsd[self.at(&(i - 1), &j, k)];
+                let (ldy, ldx): (&Block, &Block) = (&self.jac[q][1], &self.jac[q][0]);
+                let mut tv: [f64; 5] = [0.0; 5];
+
+                for m in 0usize..5
+                {
+                    tv[m] = self.rsd[point][m] -
+                            omega * (ldy[m][0] * south[0] + ldx[m][0] * west[0] +
+                                     ldy[m][1] * south[1] + ldx[m][1] * west[1] +
+                                     ldy[m][2] * south[2] + ldx[m][2] * west[2] +
+                                     ldy[m][3] * south[3] + ldx[m][3] * west[3] +
+                                     ldy[m][4] * south[4] + ldx[m][4] * west[4]);
+                }
+
+                // diagonal block inversion
+                let mut tmat: Block = self.jac[q][3];
+                LU::solve_block(&mut tmat, &mut tv);
+                self.rsd[point] = tv;
+            }
+        }
+
+        self.stop_timer(&T_BLTS, &moment);
+    }
+
+    // compute the regular-sparse, block upper triangular solution
+    fn buts( &mut self, k: &usize )
+    {
+        let moment: Instant = self.start_timer();
+        let [nx, ny, _] = self.n;
+        let omega: f64 = self.omega;
+
+        for j in (1usize..(ny - 1)).rev()
+        {
+            for i in (1usize..(nx - 1)).rev()
+            {
+                let q: usize = self.in_plane(&i, &j);
+                let above: [f64; 5] = self.rsd[self.at(&i, &j, &(k + 1))];
+                let udz: &Block = &self.jac[q][2];
+                for (m, row) in udz.iter().enumerate()
+                {
+                    self.tv[q][m] = omega * (row[0] * above[0] + row[1] * above[1] +
+                                             row[2] * above[2] + row[3] * above[3] +
+                                             row[4] * above[4]);
+                }
+            }
+        }
+
+        for j in (1usize..(ny - 1)).rev()
+        {
+            for i in (1usize..(nx - 1)).rev()
+            {
+                let (q, point): (usize, usize) = (self.in_plane(&i, &j), self.at(&i, &j, k));
+                let north: [f64; 5] = self.rsd[self.at(&i, &(j + 1), k)];
+                let east: [f64; 5] = self.rsd[self.at(&(i + 1), &j, k)];
+                let (udy, udx): (&Block, &Block) = (&self.jac[q][1], &self.jac[q][0]);
+                let mut tv: [f64; 5] = self.tv[q];
+
+                for m in 0usize..5
+                {
+                    tv[m] += omega * (udy[m][0] * north[0] + udx[m][0] * east[0] +
+                                     udy[m][1] * north[1] + udx[m][1] * east[1] +
+                                     udy[m][2] * north[2] + udx[m][2] * east[2] +
+                                     udy[m][3] * north[3] + udx[m][3] * east[3] +
+                                     udy[m][4] * north[4] + udx[m][4] * east[4]);
+                }
+
+                // diagonal block inversion
+                let mut tmat: Block = self.jac[q][3];
+                LU::solve_block(&mut tmat, &mut tv);
+                self.tv[q] = tv;
+                for (rsd_m, tv_m) in self.rsd[point].iter_mut().zip(tv.iter()) { *rsd_m -= tv_m; }
+            }
+        }
+
+        self.stop_timer(&T_BUTS, &moment);
+    }
+
+	/* ********************************************************************* */
+
+    /* computes the l2-norm of vector v (rsd, usually), over the interior of
+     * the grid
+     */
+    fn l2norm( &self, v: &[[f64; 5]], sum: &mut [f64; 5] )
+    {
+        let [nx, ny, nz] = self.n;
+
+        *sum = [0.0; 5];
+        for k in 1usize..(nz - 1)
+        {
+            for j in 1usize..(ny - 1)
+            {
+                for i in 1usize..(nx - 1)
+                {
+                    let point: usize = self.at(&i, &j, &k);
+                    for m in 0usize..5 { sum[m] += v[point][m] * v[point][m]; }
+                }
+            }
+        }
+
+        for sum_m in sum.iter_mut()
+        {
+            *sum_m = (*sum_m / (((nx - 2) * (ny - 2) * (nz - 2)) as f64)).sqrt();
+        }
+    }
+
+    /* performs the SSOR iterations; returns the number of microseconds taken
+     * by the time stepping loop
+     */
+    fn ssor<B: ParallelBackend>( &mut self, backend: &B, niter: &usize,
+                                 rsdnm: &mut [f64; 5] ) -> u128
+    {
+        let nz: usize = self.n[2];
+        let tolrsd: [f64; 5] = [1.0e-08; 5];
+        let tmp: f64 = 1.0 / (self.omega * (2.0 - self.omega));
+        let mut delunm: [f64; 5] = [0.0; 5];
+
+        // compute the steady-state residuals and their l2 norms
+        self.rhs(backend);
+        let rsd: Vec<[f64; 5]> = std::mem::take(&mut self.rsd);
+        self.l2norm(&rsd, rsdnm);
+        self.rsd = rsd;
+
+        self.timers = [0; T_LAST];
+        let start_moment: Instant = Instant::now();
+
+        // the timestep loop
+        for istep in 1usize..=*niter
+        {
+            if self.progress && (*niter > 1) &&
+               (((istep % 20) == 0) || (istep == *niter) || (istep == 1))
+            {
+                println!(" Time step {:>4}", istep);
+            }
+
+            // perform SSOR iteration
+            let moment: Instant = self.start_timer();
+            let dt: f64 = self.dt;
+            LU::for_interior(&self.n, backend, &mut self.rsd, |_, rsd|
+            {
+                for rsd_m in rsd.iter_mut() { *rsd_m *= dt; }
+            });
+            self.stop_timer(&T_ADD, &moment);
+
+            for k in 1usize..(nz - 1)
+            {
+                // form the lower triangular part of the jacobian matrix
+                self.jacld(backend, &k);
+
+                // perform the lower triangular solution
+                self.blts(&k);
+            }
+
+            for k in (1usize..(nz - 1)).rev()
+            {
+                // form the strictly upper triangular part of the jacobian matrix
+                self.jacu(backend, &k);
+
+                // perform the upper triangular solution
+                self.buts(&k);
+            }
+
+            // update the variables
+            let moment: Instant = self.start_timer();
+            let mut u: Vec<[f64; 5]> = std::mem::take(&mut self.u);
+            let rsd: &Vec<[f64; 5]> = &self.rsd;
+            LU::for_interior(&self.n, backend, &mut u, |point, u|
+            {
+                for m in 0usize..5 { u[m] += tmp * rsd[point][m]; }
+            });
+            self.u = u;
+            self.stop_timer(&T_ADD, &moment);
+
+            // compute the max-norms of newton iteration corrections
+            if (istep % *niter) == 0
+            {
+                self.l2norm(&self.rsd, &mut delunm);
+            }
+
+            // compute the steady-state residuals
+            self.rhs(backend);
+
+            // compute the max-norms of newton iteration residuals
+            if (istep % *niter) == 0
+            {
+                self.l2norm(&self.rsd, rsdnm);
+            }
+
+            // check the newton-iteration residuals against the tolerance levels
+            if (0usize..5).all(|m| rsdnm[m] < tolrsd[m])
+            {
+                if self.progress
+                {
+                    println!("\n convergence was achieved after {:>4} pseudo-time steps", istep);
+                }
+                break;
+            }
+        }
+
+        return start_moment.elapsed().as_micros();
+    }
+
+    // compute the solution error
+    fn error( &self, errnm: &mut [f64; 5] )
+    {
+        let [nx, ny, nz] = self.n;
+        let mut u000ijk: [f64; 5] = [0.0; 5];
+
+        *errnm = [0.0; 5];
+        for k in 1usize..(nz - 1)
+        {
+            for j in 1usize..(ny - 1)
+            {
+                for i in 1usize..(nx - 1)
+                {
+                    self.exact(&i, &j, &k, &mut u000ijk);
+                    let point: usize = self.at(&i, &j, &k);
+                    for m in 0usize..5
+                    {
+                        let tmp: f64 = u000ijk[m] - self.u[point][m];
+                        errnm[m] += tmp * tmp;
+                    }
+                }
+            }
+        }
+
+        for errnm_m in errnm.iter_mut()
+        {
+            *errnm_m = (*errnm_m / (((nx - 2) * (ny - 2) * (nz - 2)) as f64)).sqrt();
+        }
+    }
+
+    // compute the surface integral
+    fn pintgr( &self ) -> f64
+    {
+        let [nx, ny, nz] = self.n;
+
+        // the extent of the integration (ii1..ii2, ji1..ji2 and ki1..ki2)
+        let (ibeg, ifin): (usize, usize) = (1, nx - 2);
+        let (jbeg, jfin): (usize, usize) = (1, ny - 3);
+        let (kbeg, kfin): (usize, usize) = (2, nz - 2);
+
+        let phi = |i: usize, j: usize, k: usize| -> f64
+        {
+            let u: [f64; 5] = self.u[self.at(&i, &j, &k)];
+            return C2 * (u[4] - 0.50 * (u[1] * u[1] + u[2] * u[2] + u[3] * u[3]) / u[0]);
+        };
+
+        /* integrates over the rectangle [abeg, afin] x [bbeg, bfin] of the two
+         * faces given by phi1 and phi2
+         */
+        let integrate = |abeg: usize, afin: usize, bbeg: usize, bfin: usize,
+                         phi1: &dyn Fn(usize, usize) -> f64,
+                         phi2: &dyn Fn(usize, usize) -> f64| -> f64
+        {
+            let mut frc: f64 = 0.0;
+            for b in bbeg..bfin
+            {
+                for a in abeg..afin
+                {
+                    frc += phi1(a, b) + phi1(a + 1, b) + phi1(a, b + 1) + phi1(a + 1, b + 1) +
+                                 phi2(a, b) + phi2(a + 1, b) + phi2(a, b + 1) + phi2(a + 1, b + 1);
+                }
+            }
+            return frc;
+        };
+
+        let frc1: f64 = self.dirs[0].h * self.dirs[1].h *
+                        integrate(ibeg, ifin, jbeg, jfin,
+                                  &|i, j| phi(i, j, kbeg), &|i, j| phi(i, j, kfin));
+        let frc2: f64 = self.dirs[0].h * self.dirs[2].h *
+                        integrate(ibeg, ifin, kbeg, kfin,
+                                  &|i, k| phi(i, jbeg, k), &|i, k| phi(i, jfin, k));
+        let frc3: f64 = self.dirs[1].h * self.dirs[2].h *
+                        integrate(jbeg, jfin, kbeg, kfin,
+                                  &|j, k| phi(ibeg, j, k), &|j, k| phi(ifin, j, k));
+
+        return 0.25 * (frc1 + frc2 + frc3);
+    }
+}
+
+
+
+// the grid, the SSOR iterations and the time step of class 'class_npb'
+pub fn class_size( class_npb: &char ) -> Option<(usize, usize, f64)>
+{
+    return match class_npb
+    {
+        'S' => Some((12, 50, 0.5)),
+        'W' => Some((33, 300, 1.5e-3)),
+        'A' => Some((64, 250, 2.0)),
+        'B' => Some((102, 250, 2.0)),
+        'C' => Some((162, 250, 2.0)),
+        'D' => Some((408, 300, 1.0)),
+        'E' => Some((1020, 300, 0.5)),
+        _ => None,
+    };
+}
+
+pub const OMEGA: f64 = 1.2; // the SSOR relaxation factor
+
+/* the reference l2-norms of the residual (xcrref) and of the error (xceref),
+ * and the reference surface integral (xciref), of the official NPB for class
+ * 'class_npb' (kept with all the digits lu.f gives them)
+ */
+#[allow(clippy::excessive_precision)]
+pub fn reference_values( class_npb: &char ) -> Option<([f64; 5], [f64; 5], f64)>
+{
+    return match class_npb
+    {
+        'S' => Some(([1.6196343210976702e-02, 2.1976745164821318e-03, 1.5179927653399185e-03,
+                      1.5029584435994323e-03, 3.4264073155896461e-02],
+                     [6.4223319957960924e-04, 8.4144342047347926e-05, 5.8588269616485186e-05,
+                      5.8474222595157350e-05, 1.3103347914111294e-03],
+                     7.8418928865937083e+00)),
+        'W' => Some(([0.1236511638192e+02, 0.1317228477799e+01, 0.2550120713095e+01,
+                      0.2326187750252e+01, 0.2826799444189e+02],
+                     [0.4867877144216e+00, 0.5064652880982e-01, 0.9281818101960e-01,
+                      0.8570126542733e-01, 0.1084277417792e+01],
+                     0.1161399311023e+02)),
+        'A' => Some(([7.7902107606689367e+02, 6.3402765259692870e+01, 1.9499249727292479e+02,
+                      1.7845301160418537e+02, 1.8384760349464247e+03],
+                     [2.9964085685471943e+01, 2.8194576365003349e+00, 7.3473412698774742e+00,
+                      6.7139225687777051e+00, 7.0715315688392578e+01],
+                     2.6030925604886277e+01)),
+        'B' => Some(([3.5532672969982736e+03, 2.6214750795310692e+02, 8.8333721850952190e+02,
+                      7.7812774739425265e+02, 7.3087969592545314e+03],
+                     [1.1401176380212709e+02, 8.1098963655421574e+00, 2.8480597317698308e+01,
+                      2.5905394567832939e+01, 2.6054907504857413e+02],
+                     4.7887162703308227e+01)),
+        'C' => Some(([1.03766980323537846e+04, 8.92212458801008552e+02, 2.56238814582660871e+03,
+                      2.19194343857831427e+03, 1.78078057261061185e+04],
+                     [2.15986399716949279e+02, 1.55789559239863600e+01, 5.41318863077207766e+01,
+                      4.82262643154045421e+01, 4.55902910043250358e+02],
+                     6.66404553572181300e+01)),
+        'D' => Some(([0.4868417937025e+05, 0.4696371050071e+04, 0.1218114549776e+05,
+                      0.1033801493461e+05, 0.7142398413817e+05],
+                     [0.3752393004482e+03, 0.3084128893659e+02, 0.9434276905469e+02,
+                      0.8230686681928e+02, 0.7002620636210e+03],
+                     0.8334101392503e+02)),
+        'E' => Some(([0.2099641687874e+06, 0.2130403143165e+05, 0.5319228789371e+05,
+                      0.4509761639833e+05, 0.2932360006590e+06],
+                     [0.4800572578333e+03, 0.4221993400184e+02, 0.1210851906824e+03,
+                      0.1047888986770e+03, 0.8363028257389e+03],
+                     0.9512163272273e+02)),
+        _ => None,
+    };
+}
+
+pub const EPSILON: f64 = 1.0e-08;
+
+fn relative_difference( value: &f64, reference: &f64 ) -> f64
+{
+    return ((value - reference) / reference).abs();
+}
+
+pub fn verify( class_npb: &char, xcr: &[f64; 5], xce: &[f64; 5], xci: &f64 ) -> Verification
+{
+    let (xcrref, xceref, xciref): ([f64; 5], [f64; 5], f64) = match reference_values(class_npb)
+    {
+        Some(values) => values,
+        None => return Verification::NotPerformed,
+    };
+
+    // a NaN difference fails too
+    let within = |(value, reference): (&f64, &f64)| -> bool
+    {
+        return relative_difference(value, reference) <= EPSILON;
+    };
+    return Verification::from(xcr.iter().zip(xcrref.iter()).all(within) &&
+                              xce.iter().zip(xceref.iter()).all(within) && within((xci, &xciref)));
+}
+
+
+
+// how to run LU
+#[derive(Clone, Debug)]
+pub struct Config
+{
+    pub class:      char,
+    pub backend:    BackendKind,
+    pub threads:    Option<usize>, // the backend's default if None
+    pub binding:    Binding,       // of the backend's threads
+    pub use_timers: bool,
+    pub progress:   bool,          // prints the time steps, as the reference NPB does
+    pub perf:       bool,          // counts the hardware events of the timed run
+}
+
+impl Config
+{
+    // a run of class 'class_npb', on 'backend' with its default threads
+    pub fn new( class_npb: &char, backend: &BackendKind ) -> Config
+    {
+        return Config
+        {
+            class: *class_npb, backend: *backend, threads: None, binding: Binding::None,
+            use_timers: false, progress: false, perf: false,
+        };
+    }
+}
+
+// the class table of LU: the grid and the SSOR iterations of a class, and the threads of a run
+#[derive(Clone, Copy, Debug)]
+pub struct LuClass
+{
+    pub class:   char,
+    pub n:       [usize; 3],
+    pub itmax:   usize,
+    pub dt:      f64,
+    pub threads: usize,
+}
+
+impl LuClass
+{
+    // the entry of the run 'config' describes
+    pub fn of( config: &Config ) -> Result<LuClass, String>
+    {
+        return match class_size(&config.class)
+        {
+            Some((problem_size, itmax, dt)) => Ok(LuClass
+                                               {
+                                                   class: config.class, n: [problem_size; 3],
+                                                   itmax, dt,
+                                                   threads: config.backend.threads(&config.threads),
+                                               }),
+            None => Err(format!("LU has no class {}", config.class)),
+        };
+    }
+}
+
+impl ClassTable for LuClass
+{
+    fn kernel( &self ) -> &'static str
+    {
+        return "LU";
+    }
+
+    fn class( &self ) -> char
+    {
+        return self.class;
+    }
+
+    fn size( &self ) -> String
+    {
+        return format!("{}x{}x{}", self.n[0], self.n[1], self.n[2]);
+    }
+
+    fn iterations( &self ) -> usize
+    {
+        return self.itmax;
+    }
+
+    /* the solution, residual and forcing terms (5 values per point; erhs
+     * builds the exact solution in the residual), the 2 auxiliary arrays,
+     * and the jacobians and the upper sweep's buffer of a plane
+     */
+    fn working_set( &self ) -> u64
+    {
+        let points: usize = self.n.iter().product();
+        let plane: usize = self.n[0] * self.n[1];
+        return (((points * ((3 * 5) + 2)) + (plane * ((4 * 25) + 5))) *
+                std::mem::size_of::<f64>()) as u64;
+    }
+
+    fn seeds( &self ) -> Vec<(&'static str, f64)>
+    {
+        return Vec::new();
+    }
+}
+
+// the results of a run of LU
+#[derive(Clone, Debug)]
+pub struct LuResult
+{
+    pub class:        char,
+    pub n:            [usize; 3],
+    pub itmax:        usize,
+    pub backend:      &'static str,
+    pub threads:      usize,
+    pub xcr:          [f64; 5],      // l2-norms of the residual
+    pub xce:          [f64; 5],      // l2-norms of the solution error
+    pub xci:          f64,           // the surface integral
+    pub time:         f64,
+    pub Mops:         f64,
+    pub timers:       [f64; T_LAST], // seconds, if timed (but T_TOTAL)
+    pub verification: Verification,
+    pub usage:        ResourceUsage,
+}
+
+impl LuResult
+{
+    // the comparison with the reference values, as lu.f prints it (none without them)
+    pub fn comparison( &self ) -> String
+    {
+        let (xcrref, xceref, xciref): ([f64; 5], [f64; 5], f64) =
+                match reference_values(&self.class)
+                {
+                    Some(values) => values,
+                    None => return String::new(),
+                };
+
+        let mut out: String = String::new();
+        out += &format!("\n Verification being performed for class {}\n", self.class);
+        out += &format!(" Accuracy setting for epsilon = {:>20.13E}\n", EPSILON);
+        for (title, values, references) in [("residual", &self.xcr, &xcrref),
+                                            ("solution error", &self.xce, &xceref)]
+        {
+            out += &format!(" Comparison of RMS-norms of {}\n", title);
+            for m in 0usize..5
+            {
+                out += &format!("          {:>2} {:>20.13E} {:>20.13E} {:>20.13E}\n", m + 1,
+                                values[m], references[m],
+                                relative_difference(&values[m], &references[m]));
+            }
+        }
+        out += " Comparison of surface integral\n";
+        out += &format!("             {:>20.13E} {:>20.13E} {:>20.13E}\n", self.xci, xciref,
+                        relative_difference(&self.xci, &xciref));
+        return out;
+    }
+}
+
+// runs LU through 'backend' as 'config' says
+fn run_on<B: ParallelBackend>( backend: &B, config: &Config, table: &LuClass ) -> LuResult
+{
+    let n: [usize; 3] = table.n;
+    let (mut rsdnm, mut errnm): ([f64; 5], [f64; 5]) = ([0.0; 5], [0.0; 5]);
+
+    let mut lu: LU = LU::new(&n, &table.dt, &OMEGA, &config.use_timers, &config.progress);
+
+    // set the boundary values, the initial values and the forcing term
+    lu.setbv();
+    lu.setiv();
+    lu.erhs(backend);
+
+    // perform one SSOR iteration to touch all data pages
+    lu.ssor(backend, &1, &mut rsdnm);
+
+    // reset the boundary and initial values
+    lu.setbv();
+    lu.setiv();
+
+    // perform the SSOR iterations
+    let sampler: UsageSampler = UsageSampler::start(&config.perf);
+    lu.timers[T_TOTAL] = lu.ssor(backend, &table.itmax, &mut rsdnm);
+    let usage: ResourceUsage = sampler.stop();
+    let time: f64 = (lu.timers[T_TOTAL] as f64) / 1e6;
+
+    // compute the solution error and the surface integral
+    lu.error(&mut errnm);
+    let frc: f64 = lu.pintgr();
+
+    let mut Mops: f64 = 0.0;
+    if time != 0.0
+    {
+        let t: f64 = ((n[0] + n[1] + n[2]) as f64) / 3.0;
+        Mops = (table.itmax as f64) *
+               ((1984.77 * (n[0] as f64) * (n[1] as f64) * (n[2] as f64)) -
+                (10923.3 * (t * t)) + (27770.9 * t) - 144010.0) /
+               (time * 1000000.0);
+    }
+
+    return LuResult
+    {
+        class:        table.class,
+        n,
+        itmax:        table.itmax,
+        backend:      backend.name(),
+        threads:      backend.num_threads(),
+        verification: verify(&table.class, &rsdnm, &errnm, &frc),
+        xcr:          rsdnm,
+        xce:          errnm,
+        xci:          frc,
+        time,
+        Mops,
+        timers:       lu.timers.map(|t| (t as f64) / 1e6),
+        usage,
+    };
+}
+
+/* runs LU as 'config' says, printing nothing but the time steps if asked to;
+ * an error if its class is unknown, or its backend unavailable
+ */
+pub fn run( config: &Config ) -> Result<LuResult, String>
+{
+    let table: LuClass = LuClass::of(config)?;
+
+    return match config.backend
+    {
+        BackendKind::Serial => Ok(run_on(&Serial, config, &table)),
+        BackendKind::ThreadsChannels =>
+            Ok(run_on(&ThreadsChannels::with_binding(&table.threads, &config.binding), config,
+                      &table)),
+        #[cfg(feature = "rayon")]
+        BackendKind::Rayon => Ok(run_on(&Rayon::with_binding(&config.threads, &config.binding),
+                                        config, &table)),
+        #[cfg(not(feature = "rayon"))]
+        BackendKind::Rayon => Err(String::from("the Rayon backend needs the \"rayon\" feature")),
+    };
+}
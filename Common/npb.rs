@@ -0,0 +1,354 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    The "npb" driver: runs the kernels of the library, for a class and a   //
+//    backend chosen at run time, either one at a time ("npb run") or as a   //
+//    whole suite described by an NPB suite.def file ("npb suite").          //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+#![allow(clippy::needless_return)]
+
+
+
+use std::env;
+use std::fs;
+use std::process;
+
+use rusty_npb::affinity::Binding;
+use rusty_npb::args;
+use rusty_npb::backend::{self, BackendKind};
+use rusty_npb::dt::{self, DtResult};
+use rusty_npb::ep::{self, EpResult};
+use rusty_npb::lu::{self, LuResult};
+use rusty_npb::preflight::{self, Preflight};
+use rusty_npb::sp::{self, SpResult};
+use rusty_npb::verification::{self, Verification};
+
+
+
+// how far the port of an NPB benchmark is
+#[derive(Clone, Copy)]
+enum Status
+{
+    Available, // run by the driver
+    NotYet,    // not ported yet
+}
+
+impl Status
+{
+    fn label( &self ) -> &'static str
+    {
+        return match self
+        {
+            Status::Available => "available",
+            Status::NotYet => "not ported yet",
+        };
+    }
+}
+
+// the NPB benchmarks, and whether the driver can run them
+const KERNELS: [(&str, &str, Status); 11] =
+[
+    ("bt", "Block Tri-diagonal solver",          Status::NotYet),
+    ("cg", "Conjugate Gradient",                 Status::NotYet),
+    ("dc", "Data Cube",                          Status::NotYet),
+    ("dt", "Data Traffic",                       Status::Available),
+    ("ep", "Embarrassingly Parallel",            Status::Available),
+    ("ft", "discrete 3D fast Fourier Transform", Status::NotYet),
+    ("is", "Integer Sort",                       Status::NotYet),
+    ("lu", "Lower-Upper Gauss-Seidel solver",    Status::Available),
+    ("mg", "Multi-Grid",                         Status::NotYet),
+    ("sp", "Scalar Penta-diagonal solver",       Status::Available),
+    ("ua", "Unstructured Adaptive mesh",         Status::NotYet),
+];
+
+
+
+// the names of the backends this build can run, for the messages
+fn backend_names() -> String
+{
+    return BackendKind::available().iter().map(|kind| kind.name().to_lowercase())
+                                   .collect::<Vec<String>>().join(", ");
+}
+
+// the outcome of running one kernel
+struct KernelRun
+{
+    kernel:       String,
+    class:        char,
+    backend:      &'static str,
+    threads:      usize,
+    seconds:      f64,
+    mops:         f64,
+    verification: Verification,
+}
+
+/* runs 'kernel' through the backend named 'backend_name', once it passed the
+ * pre-flight check; None if only its plan was asked for ('graph' is DT's)
+ */
+fn run( kernel: &str, class_npb: &char, backend_name: &str, threads: &Option<usize>,
+        binding: &Binding, graph: &str, preflight: &Preflight ) -> Result<Option<KernelRun>, String>
+{
+    let backend: BackendKind = BackendKind::from_name(backend_name)?;
+    return match kernel.to_lowercase().as_str()
+    {
+        "ep" =>
+        {
+            let config: ep::Config = ep::Config
+            {
+                threads: *threads, binding: binding.clone(),
+                ..ep::Config::new(class_npb, &backend)
+            };
+            let table: ep::EpClass = ep::EpClass::of(&config)?;
+            if preflight.dry_run { preflight::print_plan(&table); return Ok(None); }
+            preflight.check(&table)?;
+            let result: EpResult = ep::run(&config)?;
+            Ok(Some(KernelRun
+            {
+                kernel:       String::from("EP"),
+                class:        result.class,
+                backend:      result.backend,
+                threads:      result.threads,
+                seconds:      result.time,
+                mops:         result.Mops,
+                verification: result.verification,
+            }))
+        }
+        "sp" =>
+        {
+            let config: sp::Config = sp::Config
+            {
+                threads: *threads, binding: binding.clone(),
+                ..sp::Config::new(class_npb, &backend)
+            };
+            let table: sp::SpClass = sp::SpClass::of(&config)?;
+            if preflight.dry_run { preflight::print_plan(&table); return Ok(None); }
+            preflight.check(&table)?;
+            let result: SpResult = sp::run(&config)?;
+            Ok(Some(KernelRun
+            {
+                kernel:       String::from("SP"),
+                class:        result.class,
+                backend:      result.backend,
+                threads:      result.threads,
+                seconds:      result.time,
+                mops:         result.Mops,
+                verification: result.verification,
+            }))
+        }
+        "lu" =>
+        {
+            let config: lu::Config = lu::Config
+            {
+                threads: *threads, binding: binding.clone(),
+                ..lu::Config::new(class_npb, &backend)
+            };
+            let table: lu::LuClass = lu::LuClass::of(&config)?;
+            if preflight.dry_run { preflight::print_plan(&table); return Ok(None); }
+            preflight.check(&table)?;
+            let result: LuResult = lu::run(&config)?;
+            Ok(Some(KernelRun
+            {
+                kernel:       String::from("LU"),
+                class:        result.class,
+                backend:      result.backend,
+                threads:      result.threads,
+                seconds:      result.time,
+                mops:         result.Mops,
+                verification: result.verification,
+            }))
+        }
+        "dt" =>
+        {
+            let config: dt::Config = dt::Config
+            {
+                threads: *threads, binding: binding.clone(),
+                ..dt::Config::new(class_npb, graph, &backend)
+            };
+            let table: dt::DtClass = dt::DtClass::of(&config)?;
+            if preflight.dry_run { preflight::print_plan(&table); return Ok(None); }
+            preflight.check(&table)?;
+            let result: DtResult = dt::run(&config)?;
+            Ok(Some(KernelRun
+            {
+                kernel:       String::from("DT"),
+                class:        result.class,
+                backend:      result.backend,
+                threads:      result.threads,
+                seconds:      result.time,
+                mops:         result.Mops,
+                verification: result.verification,
+            }))
+        }
+        kernel => match KERNELS.iter().find(|k| k.0 == kernel)
+        {
+            Some((_, _, status)) => Err(format!("{} is {}", kernel.to_uppercase(), status.label())),
+            None => Err(format!("unknown kernel \"{}\"", kernel)),
+        },
+    };
+}
+
+/* the kernel/class pairs of an NPB suite.def file (one "kernel class" pair
+ * per line, '#' starting comments; anything after the class is ignored)
+ */
+fn read_suite( path: &str ) -> Result<Vec<(String, char)>, String>
+{
+    let contents: String = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let mut entries: Vec<(String, char)> = Vec::new();
+    for (number, line) in contents.lines().enumerate()
+    {
+        let line: &str = line.split('#').next().unwrap_or("");
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.is_empty() { continue; }
+        match (fields.first(), fields.get(1))
+        {
+            (Some(kernel), Some(class)) if class.len() == 1 =>
+                entries.push((kernel.to_lowercase(), class.to_ascii_uppercase().as_bytes()[0] as char)),
+            _ => return Err(format!("{}:{}: expected \"kernel class\"", path, number + 1)),
+        }
+    }
+    return Ok(entries);
+}
+
+fn print_run( run: &KernelRun )
+{
+	println!(" {} class {} ({}, {} threads): {:.4} s, {:.2} Mop/s, {}",
+	        run.kernel, run.class, run.backend, run.threads, run.seconds, run.mops,
+	        run.verification.label());
+}
+
+fn print_summary( runs: &[KernelRun] )
+{
+	println!("\n Summary:\n");
+	println!(" {:<6} {:>5} {:>8} {:>7} {:>12} {:>12} {:>14}",
+	        "Kernel", "Class", "Backend", "Threads", "Time (s)", "Mop/s", "Verification");
+	for run in runs
+	{
+	    println!(" {:<6} {:>5} {:>8} {:>7} {:>12.4} {:>12.2} {:>14}",
+	            run.kernel, run.class, run.backend, run.threads, run.seconds, run.mops,
+	            run.verification.label());
+	}
+	println!();
+}
+
+fn print_usage()
+{
+	println!(" ** Usage: npb run KERNEL [--class C] [--backend B] [--threads N] [--bind P]");
+	println!("                          [--graph G] [--force] [--dry-run]");
+	println!("           npb suite SUITE.DEF [--backend B] [--threads N] [--bind P]");
+	println!("                               [--graph G] [--force] [--dry-run]");
+	println!("           npb list");
+	println!("    backends: {}", backend_names());
+	println!("    graphs (of DT): BH (the default), WH or SH");
+}
+
+
+
+fn main()
+{
+    // processing command line arguments
+    let args: Vec<String> = env::args().collect::<Vec<String>>()[1..].to_vec();
+    let class_npb: char =
+            match args::or_exit(args::option_value(&args, "--class"))
+            {
+                Some(class) if class.len() == 1 => class.to_ascii_uppercase().as_bytes()[0] as char,
+                Some(class) => args::or_exit(Err(format!("invalid class \"{}\"", class))),
+                None => 'S',
+            };
+    let backend_name: &str =
+            args::or_exit(args::option_value(&args, "--backend")).unwrap_or("serial");
+    let threads: Option<usize> = args::or_exit(backend::requested_threads(&args));
+    let binding: Binding = args::or_exit(Binding::from_args(&args));
+    let graph: &str = args::or_exit(args::option_value(&args, "--graph")).unwrap_or("BH");
+    let preflight: Preflight = Preflight::from_args(&args);
+
+	/* ********************************************************************* */
+
+    let mut runs: Vec<KernelRun> = Vec::new();
+    let mut failures: bool = false; // suite entries that could not be run
+    match (args.first().map(|a| a.as_str()), args.get(1))
+    {
+        (Some("list"), _) =>
+        {
+	        println!("\n Kernels (run by the driver for the backends {}):\n",
+	                backend_names());
+	        for (name, description, status) in KERNELS
+	        {
+	            println!("    {:<4} {:<36} {}", name, description, status.label());
+	        }
+	        println!();
+	        return;
+        }
+        (Some("run"), Some(kernel)) =>
+        {
+            if let Some(run) = args::or_exit(run(kernel, &class_npb, backend_name, &threads,
+                                                 &binding, graph, &preflight))
+            {
+                print_run(&run);
+                runs.push(run);
+            }
+        }
+        (Some("suite"), Some(path)) =>
+        {
+            for (kernel, class) in args::or_exit(read_suite(path))
+            {
+                match run(&kernel, &class, backend_name, &threads, &binding, graph, &preflight)
+                {
+                    Ok(Some(run)) => { print_run(&run); runs.push(run); }
+                    Ok(None) => {}
+                    Err(message) =>
+                    {
+                        eprintln!(" {} class {}: {}", kernel.to_uppercase(), class, message);
+                        failures = true;
+                    }
+                }
+            }
+        }
+        _ =>
+        {
+            print_usage();
+            process::exit(verification::EXIT_INVALID);
+        }
+    }
+
+    if preflight.dry_run
+    {
+        if failures { process::exit(verification::EXIT_INVALID); }
+        return;
+    }
+    print_summary(&runs);
+
+    // the worst outcome of all the runs
+    let outcome: Verification = runs.iter().fold(Verification::Successful,
+                                                 |outcome, run| outcome.and(run.verification));
+    if failures && outcome.passed() { process::exit(verification::EXIT_INVALID); }
+    process::exit(outcome.exit_code());
+}
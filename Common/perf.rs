@@ -0,0 +1,277 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    Hardware performance counters of the timed region (cycles,             //
+//    instructions, cache and branch misses, floating point instructions),   //
+//    counted through the raw perf_event_open syscall on every thread of the //
+//    process. Any event the kernel, the CPU or the container refuses is     //
+//    simply reported as unavailable.                                        //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+use std::fs;
+
+
+
+// the counts of the events, None for the unavailable ones
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PerfCounts
+{
+    pub cycles:        Option<u64>,
+    pub instructions:  Option<u64>,
+    pub cache_misses:  Option<u64>,
+    pub branch_misses: Option<u64>,
+    pub fp_ops:        Option<u64>, // double precision FP instructions (Intel only)
+}
+
+// the events counted: their name, and their perf type and config
+#[cfg(target_os = "linux")]
+fn events() -> Vec<(&'static str, u32, u64)>
+{
+    const PERF_TYPE_HARDWARE: u32 = 0;
+    const PERF_TYPE_RAW: u32 = 4;
+
+    let mut events: Vec<(&'static str, u32, u64)> = vec![("cycles", PERF_TYPE_HARDWARE, 0),
+                                                         ("instructions", PERF_TYPE_HARDWARE, 1),
+                                                         ("cache-misses", PERF_TYPE_HARDWARE, 3),
+                                                         ("branch-misses", PERF_TYPE_HARDWARE, 5)];
+
+    /* there is no generic FP event: FP_ARITH_INST_RETIRED (0xC7) of Intel
+     * CPUs, for its scalar, 128, 256 and 512-bit double precision forms
+     */
+    let intel: bool = fs::read_to_string("/proc/cpuinfo").map(|info| info.contains("GenuineIntel"))
+                                                         .unwrap_or(false);
+    if intel && cfg!(target_arch = "x86_64") { events.push(("fp-ops", PERF_TYPE_RAW, 0x55C7)); }
+    return events;
+}
+
+// struct perf_event_attr (PERF_ATTR_SIZE_VER5) of linux/perf_event.h
+#[cfg(target_os = "linux")]
+#[repr(C)]
+#[derive(Default)]
+struct PerfEventAttr
+{
+    kind:               u32,
+    size:               u32,
+    config:             u64,
+    sample_period:      u64,
+    sample_type:        u64,
+    read_format:        u64,
+    flags:              u64, // disabled, inherit, pinned, exclusive, exclude_user, ...
+    wakeup_events:      u32,
+    bp_type:            u32,
+    config1:            u64,
+    config2:            u64,
+    branch_sample_type: u64,
+    sample_regs_user:   u64,
+    sample_stack_user:  u32,
+    clockid:            i32,
+    sample_regs_intr:   u64,
+    aux_watermark:      u32,
+    sample_max_stack:   u16,
+    reserved:           u16,
+}
+
+/* opens a counter of an event for thread 'tid' and the threads it creates
+ * from then on, in user space only (which perf_event_paranoid allows more
+ * often); None if refused
+ */
+#[cfg(target_os = "linux")]
+fn open_counter( kind: &u32, config: &u64, tid: &i32 ) -> Option<i32>
+{
+    const INHERIT: u64 = 1 << 1;
+    const EXCLUDE_KERNEL: u64 = 1 << 5;
+    const EXCLUDE_HV: u64 = 1 << 6;
+    const FORMAT_TOTAL_TIMES: u64 = 1 | 2; // enabled and running times, for multiplexing
+    const FLAG_FD_CLOEXEC: libc::c_ulong = 1 << 3;
+
+    let attr: PerfEventAttr = PerfEventAttr
+    {
+        kind: *kind, size: std::mem::size_of::<PerfEventAttr>() as u32, config: *config,
+        read_format: FORMAT_TOTAL_TIMES, flags: INHERIT | EXCLUDE_KERNEL | EXCLUDE_HV,
+        ..PerfEventAttr::default()
+    };
+    // SAFETY: 'attr' is a properly sized and initialized perf_event_attr
+    let fd: libc::c_long = unsafe
+    {
+        libc::syscall(libc::SYS_perf_event_open, &attr as *const PerfEventAttr, *tid,
+                      -1 as libc::c_int, -1 as libc::c_int, FLAG_FD_CLOEXEC)
+    };
+    return if fd >= 0 { Some(fd as i32) } else { None };
+}
+
+/* the count of a counter, scaled up if it was multiplexed with others; None
+ * if it could not be read or never ran
+ */
+#[cfg(target_os = "linux")]
+fn read_counter( fd: &i32 ) -> Option<u64>
+{
+    let mut values: [u64; 3] = [0; 3]; // value, time enabled, time running
+    let size: usize = std::mem::size_of::<[u64; 3]>();
+    // SAFETY: 'values' is writable for the 'size' bytes read into it
+    let read: isize = unsafe { libc::read(*fd, values.as_mut_ptr() as *mut libc::c_void, size) };
+    if (read != (size as isize)) || (values[2] == 0) { return None; }
+    return Some(((values[0] as f64) * (values[1] as f64) / (values[2] as f64)) as u64);
+}
+
+// the ids of the threads of the process
+#[cfg(target_os = "linux")]
+fn threads() -> Vec<i32>
+{
+    return match fs::read_dir("/proc/self/task")
+    {
+        Ok(entries) => entries.filter_map(|entry| entry.ok()?.file_name().to_str()?.parse::<i32>().ok())
+                              .collect(),
+        Err(_) => Vec::new(),
+    };
+}
+
+
+
+/* the counters of every available event, on every thread of the process
+ * (those there when they were opened, and those they created afterwards)
+ */
+pub struct PerfCounters
+{
+    counters: Vec<(&'static str, Vec<i32>)>, // an event, and its counters
+}
+
+impl PerfCounters
+{
+    // starts counting
+    #[cfg(target_os = "linux")]
+    pub fn start() -> PerfCounters
+    {
+        let threads: Vec<i32> = threads();
+        let counters: Vec<(&'static str, Vec<i32>)> = events().iter().filter_map(|(name, kind, config)|
+        {
+            let fds: Vec<i32> = threads.iter().filter_map(|tid| open_counter(kind, config, tid)).collect();
+            return if fds.is_empty() { None } else { Some((*name, fds)) };
+        }).collect();
+        return PerfCounters { counters };
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn start() -> PerfCounters
+    {
+        return PerfCounters { counters: Vec::new() };
+    }
+
+    // the count of 'event' so far, summed over the threads
+    #[cfg(target_os = "linux")]
+    fn count( &self, event: &str ) -> Option<u64>
+    {
+        let (_, fds) = self.counters.iter().find(|(name, _)| *name == event)?;
+        return fds.iter().map(read_counter).sum::<Option<u64>>();
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn count( &self, _event: &str ) -> Option<u64>
+    {
+        return None;
+    }
+
+    // the counts so far
+    pub fn read( &self ) -> PerfCounts
+    {
+        return PerfCounts
+        {
+            cycles:        self.count("cycles"),
+            instructions:  self.count("instructions"),
+            cache_misses:  self.count("cache-misses"),
+            branch_misses: self.count("branch-misses"),
+            fp_ops:        self.count("fp-ops"),
+        };
+    }
+}
+
+impl Drop for PerfCounters
+{
+    fn drop( &mut self )
+    {
+        for fd in self.counters.iter().flat_map(|(_, fds)| fds.iter())
+        {
+            // SAFETY: every descriptor was opened by start(), and is closed once
+            unsafe { libc::close(*fd); }
+        }
+    }
+}
+
+
+
+impl PerfCounts
+{
+    pub fn available( &self ) -> bool
+    {
+        return [self.cycles, self.instructions, self.cache_misses, self.branch_misses, self.fp_ops]
+                .iter().any(|count| count.is_some());
+    }
+
+    /* the counter lines of the report, with the instructions per cycle and
+     * the misses per million operations ('mop' of them in the region)
+     */
+    pub fn render( &self, mop: &f64 ) -> String
+    {
+        let show = |value: Option<String>| -> String { value.unwrap_or(String::from("unavailable")) };
+        let per_mop = |count: Option<u64>| -> Option<String>
+        {
+            return count.filter(|_| *mop > 0.0).map(|c| format!("{:.2}", (c as f64) / mop));
+        };
+        let ipc: Option<String> = self.instructions.zip(self.cycles).filter(|(_, cycles)| *cycles > 0)
+                                      .map(|(i, c)| format!("{:.3}", (i as f64) / (c as f64)));
+
+        if !self.available()
+        {
+	        return format!(" HW counters     = {:>30}\n", "unavailable");
+        }
+        let mut out: String = String::new();
+	    out += &format!(" Cycles          = {:>30}\n", show(self.cycles.map(|c| c.to_string())));
+	    out += &format!(" Instructions    = {:>30}\n",
+	                    show(self.instructions.map(|c| c.to_string())));
+	    out += &format!(" IPC             = {:>30}\n", show(ipc));
+	    out += &format!(" Cache miss/Mop  = {:>30}\n", show(per_mop(self.cache_misses)));
+	    out += &format!(" Branch miss/Mop = {:>30}\n", show(per_mop(self.branch_misses)));
+	    out += &format!(" FP ops          = {:>30}\n", show(self.fp_ops.map(|c| c.to_string())));
+        return out;
+    }
+
+    // as a JSON object (null for the unavailable counts)
+    pub fn to_json( &self ) -> String
+    {
+        let value = |count: Option<u64>| -> String
+        {
+            return count.map(|c| c.to_string()).unwrap_or(String::from("null"));
+        };
+        return format!("{{ \"cycles\": {}, \"instructions\": {}, \"cache_misses\": {}, \
+                        \"branch_misses\": {}, \"fp_ops\": {} }}",
+                       value(self.cycles), value(self.instructions), value(self.cache_misses),
+                       value(self.branch_misses), value(self.fp_ops));
+    }
+}
@@ -0,0 +1,139 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    What a run will need, checked before anything is allocated: every      //
+//    kernel's class table estimates its working set, which is compared with //
+//    the memory available (MemAvailable) so that runs too large for the node//
+//    are refused instead of swapping or being killed partway through.       //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+use crate::host;
+
+
+
+// the pre-flight options of a run
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Preflight
+{
+    pub force:   bool, // start even if the working set does not seem to fit
+    pub dry_run: bool, // only print the plan, without running anything
+}
+
+/* the parameters of a class of a kernel, as its class table gives them:
+ * enough to tell what a run will do, and how much memory it will take,
+ * before any of it is allocated
+ */
+pub trait ClassTable
+{
+    fn kernel( &self ) -> &'static str;
+
+    fn class( &self ) -> char;
+
+    // the size of the problem, as the beginning message describes it
+    fn size( &self ) -> String;
+
+    fn iterations( &self ) -> usize;
+
+    // the estimated working set of a run, in bytes
+    fn working_set( &self ) -> u64;
+
+    // the seeds (and multipliers) of the random number generators, if any
+    fn seeds( &self ) -> Vec<(&'static str, f64)>;
+}
+
+
+
+fn mib( bytes: &u64 ) -> f64
+{
+    return (*bytes as f64) / 1048576.0;
+}
+
+// the memory that can be allocated without swapping (MemAvailable), in bytes
+pub fn available_memory() -> Option<u64>
+{
+    return host::meminfo("MemAvailable").map(|kb| kb * 1024);
+}
+
+// the plan of a run of 'table' (all that --dry-run prints)
+pub fn print_plan( table: &dyn ClassTable )
+{
+    let seeds: Vec<String> = table.seeds().iter()
+                                  .map(|(name, seed)| format!("{} = {:.0}", name, seed)).collect();
+	println!("\n {} Benchmark plan (class {})\n", table.kernel(), table.class());
+	println!(" Size:               {}", table.size());
+	println!(" Iterations:         {}", table.iterations());
+	println!(" Estimated memory:   {:.2} MiB", mib(&table.working_set()));
+	println!(" RNG seeds:          {}\n",
+	        if seeds.is_empty() { String::from("(none)") } else { seeds.join(", ") });
+}
+
+impl Preflight
+{
+    // --force and --dry-run
+    pub fn from_args( args: &[String] ) -> Preflight
+    {
+        return Preflight
+        {
+            force:   args.iter().any(|arg| arg == "--force"),
+            dry_run: args.iter().any(|arg| arg == "--dry-run"),
+        };
+    }
+
+    /* prints the estimated working set of 'table' against the available
+     * memory; an error if it does not fit, unless forced (or if the available
+     * memory is unknown)
+     */
+    pub fn check( &self, table: &dyn ClassTable ) -> Result<(), String>
+    {
+        let needed: u64 = table.working_set();
+        let available: u64 = match available_memory()
+        {
+            Some(available) => available,
+            None =>
+            {
+	            println!(" Estimated memory:                   {:.2} MiB", mib(&needed));
+                return Ok(());
+            },
+        };
+
+	    println!(" Estimated memory:                   {:.2} MiB ({:.2} MiB available)",
+	            mib(&needed), mib(&available));
+        if needed <= available { return Ok(()); }
+        if !self.force
+        {
+            return Err(format!("{} class {} would need about {:.2} MiB, but only {:.2} MiB are \
+                                available (--force starts it anyway)",
+                               table.kernel(), table.class(), mib(&needed), mib(&available)));
+        }
+	    println!(" Warning: the estimated memory exceeds the available memory (--force)");
+        return Ok(());
+    }
+}
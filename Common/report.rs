@@ -0,0 +1,277 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    The results block every benchmark ends with (print_results), built     //
+//    from the values of the run, of the build (which each package records   //
+//    at compile time) and of the host, and rendered as a string, so that    //
+//    its layout can be checked against a saved copy of it; or rendered as   //
+//    the reference NPB 3.4.1 prints it, for the scripts that parse those.   //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+use crate::host::HostInfo;
+use crate::usage::ResourceUsage;
+use crate::verification::Verification;
+
+
+
+// the build of the benchmark, as recorded by its package's build.rs
+#[derive(Clone, Debug)]
+pub struct BuildInfo
+{
+    pub package:         &'static str,
+    pub authors:         &'static str,         // separated by ':', as cargo gives them
+    pub version:         &'static str,
+    pub compile_date:    &'static str,
+    pub rustc_version:   &'static str,         // as "rustc --version" prints it
+    pub rustc:           &'static str,
+    pub rustflags:       &'static str,         // empty if none
+    pub profile:         &'static str,
+    pub target:          &'static str,
+    pub target_cpu:      &'static str,
+    pub target_features: &'static str,
+    pub dependencies:    Vec<&'static str>,    // "name version"
+    pub rayon_version:   Option<&'static str>, // of the Rayon version only
+}
+
+// the version of the NPB these benchmarks are a port of, as its reports give it
+pub const NPB_VERSION: &str = "3.4.1";
+
+// the benchmarks the reference NPB writes in C (whose reports differ slightly)
+const C_BENCHMARKS: [&str; 3] = ["IS", "DC", "DT"];
+
+/* 'x' in the Fortran edit descriptor "1P,Dw.d" (one digit before the point,
+ * 'digits' after it, and a two-digit exponent), right-aligned to 'width'
+ */
+pub fn fortran_d( x: &f64, width: &usize, digits: &usize ) -> String
+{
+    let scientific: String = format!("{:.*e}", *digits, x);
+    let (mantissa, exponent): (&str, &str) = scientific.split_once('e').unwrap();
+    let exponent: i32 = exponent.parse().unwrap();
+    let d: String = format!("{}D{}{:02}", mantissa, if exponent < 0 { '-' } else { '+' },
+                            exponent.abs());
+    return format!("{:>1$}", d, *width);
+}
+
+// 'x' in the Fortran edit descriptor "Fw.0" (which keeps the point)
+pub fn fortran_f0( x: &f64, width: &usize ) -> String
+{
+    return format!("{:>1$}", format!("{:.0}.", x), *width);
+}
+
+// a "YYYY-MM-DD" date as the NPB's builds record it ("DD Mon YYYY")
+fn npb_date( date: &str ) -> String
+{
+    const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun",
+                                "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+    let parts: Vec<&str> = date.split('-').collect();
+    return match parts.get(1).and_then(|month| month.parse::<usize>().ok())
+    {
+        Some(month) if (parts.len() == 3) && (1..=12).contains(&month) =>
+            format!("{} {} {}", parts[2], MONTHS[month - 1], parts[0]),
+        _ => date.to_string(),
+    };
+}
+
+pub struct BenchmarkReport
+{
+    pub name:              String,
+    pub class:             char,
+    pub size:              String,        // see BenchmarkReport::size
+    pub total_threads:     String,
+    pub requested_threads: Option<usize>,
+    pub available_threads: Option<usize>, // to the run (OpenMP's max threads), None if serial
+    pub iterations:        i64,
+    pub time:              f64,
+    pub mops:              f64,
+    pub optype:            String,
+    pub verification:      Verification,
+    pub usage:             ResourceUsage,
+    pub build:             BuildInfo,
+    pub host:              HostInfo,
+}
+
+impl BenchmarkReport
+{
+    /* the size of a problem of dimensions 'n1' x 'n2' x 'n3' of the benchmark
+     * 'name' (2^n1 for EP, n1 x n2 for IS, and just n1 if the others are 0)
+     */
+    pub fn size( name: &str, n1: &i64, n2: &i64, n3: &i64 ) -> String
+    {
+	    return
+	        if (name == "IS") && (*n3 == 0) {
+	            (n1 * (if *n2 != 0 { n2 } else { &1 })).to_string() }
+	        else if (*n2 == 0) && (*n3 == 0) {
+	            if name == "EP" { (1i128 << n1).to_string() }
+	            else { n1.to_string() } }
+	        else {
+	            format!("{:>4}x{:>4}x{:>4}", n1, n2, n3) };
+    }
+
+    pub fn render( &self ) -> String
+    {
+        let authors: String = self.build.authors.replace(":", "\n                            ");
+        let rustflags: &str =
+                if self.build.rustflags.is_empty() { "(none)" } else { self.build.rustflags };
+        let mut out: String = String::new();
+
+	    out += &format!("\n\n {} Benchmark Completed\n\n", self.name);
+	    out += &format!(" class_npb       = {:>30}\n", self.class);
+	    out += &format!(" Size            = {:>30}\n", self.size);
+	    out += &format!(" Total threads   = {:>30}\n", self.total_threads);
+	    if let Some(requested) = self.requested_threads
+	    {
+	        out += &format!(" Req. threads    = {:>30}\n", requested);
+	    }
+	    out += &format!(" Iterations      = {:>30}\n", self.iterations);
+	    out += &format!(" Time in seconds = {:>30}\n", self.time);
+	    out += &self.usage.render();
+	    out += &format!(" Mop/s total     = {:>30}\n", self.mops);
+	    out += &self.usage.render_counters(&(self.mops * self.time));
+	    out += &format!(" Operation type  = {:>30}\n", self.optype);
+	    out += &format!(" Verification    = {:>30}\n", self.verification.label());
+	    out += &format!(" Version         = {:>30}\n", self.build.version);
+	    out += &format!(" Compile date    = {:>30}\n", self.build.compile_date);
+	    out += &format!(" Compiler ver    = {:>30}\n",
+	                    self.build.rustc_version.trim_start_matches("rustc "));
+	    if let Some(rayon_version) = self.build.rayon_version
+	    {
+	        out += &format!(" Rayon version   = {:>30}\n", rayon_version);
+	    }
+	    out += "\n Compile options:\n";
+	    out += &format!("    RUSTC        = {:>30}\n", self.build.rustc);
+	    out += &format!("    RUSTFLAGS    = {:>30}\n", rustflags);
+	    out += &format!("    PROFILE      = {:>30}\n", self.build.profile);
+	    out += &format!("    TARGET       = {:>30}\n", self.build.target);
+	    out += &format!("    TARGET CPU   = {:>30}\n", self.build.target_cpu);
+	    out += &format!("    FEATURES     = {:>30}\n", self.build.target_features);
+	    out += &format!("    RAND         = {:>30}\n", "f64rand");
+	    for (i, dependency) in self.build.dependencies.iter().enumerate()
+	    {
+	        out += &format!("    {:<12} {} {:>30}\n", if i == 0 { "DEPENDENCIES" } else { "" },
+	                        if i == 0 { "=" } else { " " }, dependency);
+	    }
+	    out += &self.host.render();
+	    if self.requested_threads.is_some_and(|requested| requested.to_string() != self.total_threads)
+	    {
+	        out += "\n Warning: Threads used differ from threads requested\n";
+	    }
+
+	    out += &format!("\n {:-<70}\n", "");
+	    out += &format!(" {} is developed by: {authors}\n",
+	                    self.build.package.replace("-", " ").replace("_", " "));
+	    out += " In case of questions or problems, please send an e-mail.\n";
+	    out += &format!(" {:-<70}\n\n", "");
+        return out;
+    }
+
+    /* the results block as the reference NPB 3.4.1 prints it, byte for byte:
+     * that of print_results.f (or of c_print_results.c, for the benchmarks it
+     * writes in C), with the thread lines of its OpenMP versions if the run
+     * was parallel; its compiler fields show rustc, the dependencies, and the
+     * profile and RUSTFLAGS of the build
+     */
+    pub fn render_npb( &self ) -> String
+    {
+        let c: bool = C_BENCHMARKS.contains(&self.name.as_str());
+        let field = |label: &str, value: &str| -> String
+        {
+            return format!(" {:<16}= {:>24}\n", label, value);
+        };
+        let option = |label: &str, value: &str| -> String
+        {
+            return format!("    {:<13}= {}\n", label, value);
+        };
+        let threads: f64 = self.total_threads.parse().unwrap_or(1.0);
+        let rustflags: String = if self.build.rustflags.is_empty() { String::new() }
+                                else { format!(" {}", self.build.rustflags) };
+        let libraries: String = if self.build.dependencies.is_empty() { String::from("(none)") }
+                                else { self.build.dependencies.join(", ") };
+        let verification: &str = match self.verification
+        {
+            Verification::Successful => "SUCCESSFUL",
+            Verification::NotPerformed if c => "NOT PERFORMED",
+            _ => "UNSUCCESSFUL",
+        };
+        let mut out: String = String::new();
+
+	    out += &format!("\n\n {} Benchmark Completed.\n", self.name);
+	    out += &field("Class", &self.class.to_string());
+	    out += &match self.size.split_once('x')
+	    {
+	        // a grid (see BenchmarkReport::size), whose line the C reports shift by two
+	        Some(_) if c => format!(" Size            =             {}\n", self.size),
+	        Some(_) => format!(" Size            =           {}\n", self.size),
+	        None => field("Size", &self.size),
+	    };
+	    out += &field("Iterations", &self.iterations.to_string());
+	    out += &field("Time in seconds", &format!("{:.2}", self.time));
+	    if let Some(available) = self.available_threads
+	    {
+	        out += &field("Total threads", &self.total_threads);
+	        out += &field("Avail threads", &available.to_string());
+	        if self.total_threads != available.to_string()
+	        {
+	            out += " Warning: Threads used differ from threads available\n";
+	        }
+	    }
+	    out += &field("Mop/s total", &format!("{:.2}", self.mops));
+	    if self.available_threads.is_some()
+	    {
+	        out += &field("Mop/s/thread", &format!("{:.2}", self.mops / threads));
+	    }
+	    out += &if c { format!(" Operation type  = {:>24}\n", self.optype) }
+	           else { format!(" Operation type  = {:<24}\n", self.optype) };
+	    out += &field("Verification", verification);
+	    out += &field("Version", NPB_VERSION);
+	    out += &field("Compile date", &npb_date(self.build.compile_date));
+	    out += "\n Compile options:\n";
+	    let (compiler, link, lib, inc, flags, linkflags): (&str, &str, &str, &str, &str, &str) =
+	            if c { ("CC", "CLINK", "C_LIB", "C_INC", "CFLAGS", "CLINKFLAGS") }
+	            else { ("FC", "FLINK", "F_LIB", "F_INC", "FFLAGS", "FLINKFLAGS") };
+	    out += &option(compiler, self.build.rustc);
+	    out += &option(link, &format!("$({})", compiler));
+	    out += &option(lib, &libraries);
+	    out += &option(inc, "(none)");
+	    out += &option(flags, &format!("{}{}", self.build.profile, rustflags));
+	    out += &option(linkflags, &format!("$({})", flags));
+	    out += &option("RAND", "f64rand");
+
+	    out += "\n\n Please send all errors/feedbacks to:\n\n";
+	    out += " NPB Development Team\n";
+	    out += if c { " npb@nas.nasa.gov\n\n" } else { " npb@nas.nasa.gov\n\n\n" };
+        return out;
+    }
+
+    pub fn print( &self )
+    {
+        print!("{}", self.render());
+    }
+}
@@ -0,0 +1,132 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    Strong-scaling sweeps: the same problem run in-process over a list of  //
+//    thread counts, and reported against the 1-thread run.                  //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+use crate::args;
+use crate::backend;
+use crate::host::HostInfo;
+use crate::verification::Verification;
+
+
+
+// one run of a sweep
+#[derive(Clone, Copy)]
+pub struct ScalingRun
+{
+    pub threads:      usize,
+    pub seconds:      f64,
+    pub mops:         f64,
+    pub verification: Verification,
+}
+
+/* the thread counts asked for through "--scaling 1,2,4,..." in 'args', or
+ * through "--scaling auto" (the powers of two up to the number of available
+ * threads, and that number itself); 1 is always included, as the reference
+ * of the speedups. None if the option is absent
+ */
+pub fn thread_counts( args: &[String] ) -> Result<Option<Vec<usize>>, String>
+{
+    let value: &str = match args::option_value(args, "--scaling")?
+    {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+
+    let mut counts: Vec<usize> = Vec::new();
+    if value == "auto"
+    {
+        let available: usize = backend::available_threads();
+        let mut threads: usize = 1;
+        while threads < available { counts.push(threads); threads *= 2; }
+        counts.push(available);
+    }
+    else
+    {
+        for item in value.split(',')
+        {
+            match item.trim().parse::<usize>()
+            {
+                Ok(threads) if threads > 0 => counts.push(threads),
+                _ => return Err(format!("invalid thread count in --scaling: \"{}\"", item)),
+            }
+        }
+    }
+    counts.push(1);
+    counts.sort_unstable();
+    counts.dedup();
+    return Ok(Some(counts));
+}
+
+// speedup and parallel efficiency of 'run' relative to the 1-thread run
+fn speedup( runs: &[ScalingRun], run: &ScalingRun ) -> (f64, f64)
+{
+    let reference: f64 =
+            runs.iter().find(|r| r.threads == 1).map_or(run.seconds, |r| r.seconds);
+    let speedup: f64 = reference / run.seconds;
+    return (speedup, speedup / (run.threads as f64));
+}
+
+pub fn print_table( runs: &[ScalingRun] )
+{
+	println!("\n Strong scaling:\n");
+	println!(" {:>7} {:>12} {:>12} {:>9} {:>10} {:>12}",
+	        "Threads", "Time (s)", "Mop/s", "Speedup", "Efficiency", "Verification");
+	for run in runs
+	{
+	    let (speedup, efficiency): (f64, f64) = speedup(runs, run);
+	    println!(" {:>7} {:>12.4} {:>12.2} {:>9.2} {:>9.1}% {:>12}",
+	            run.threads, run.seconds, run.mops, speedup, efficiency * 100.0,
+	            run.verification.label());
+	}
+	println!();
+}
+
+// the sweep as a JSON document
+pub fn to_json( name: &str, class_npb: &char, backend: &str, runs: &[ScalingRun] ) -> String
+{
+    let mut json: String = format!("{{\n  \"benchmark\": \"{}\",\n  \"class\": \"{}\",\n  \
+                                    \"backend\": \"{}\",\n  \"scaling\": [",
+                                   name, class_npb, backend);
+    for (i, run) in runs.iter().enumerate()
+    {
+        let (speedup, efficiency): (f64, f64) = speedup(runs, run);
+        json += if i == 0 { "\n" } else { ",\n" };
+        json += &format!("    {{ \"threads\": {}, \"seconds\": {:e}, \"mops\": {:e}, \
+                          \"speedup\": {:e}, \"efficiency\": {:e}, \"verification\": \"{}\" }}",
+                         run.threads, run.seconds, run.mops, speedup, efficiency,
+                         run.verification.label());
+    }
+    json += &format!("\n  ],\n  \"host\": {}\n}}\n", HostInfo::collect().to_json());
+    return json;
+}
@@ -0,0 +1,1429 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//               Rust version of the pseudo-application "SP",                //
+//               or "scalar penta-diagonal" solver benchmark.                //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//   The original NPB 3.4.1 version was written in Fortran and belongs to:   //
+//                   http://www.nas.nasa.gov/Software/NPB/                   //
+//                     Authors of the Fortran code are:                      //
+//                  R. Van der Wijngaart, W. Saphir, H. Jin                  //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    This benchmark solves three sets of uncoupled systems of equations,    //
+//    resulting from an approximate factorization of the Navier-Stokes       //
+//    equations (Beam-Warming), first in the x, then in the y and finally in //
+//    the z direction. After diagonalization, each of these systems is       //
+//    scalar and penta-diagonal. The planes of the grid are spread over the  //
+//    threads of the backend, but for the z lines, which cross them: those   //
+//    are spread by rows, solved into buffers of the threads and copied      //
+//    back.                                                                  //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+#![allow(non_snake_case)]
+
+use std::time::Instant;
+
+use crate::affinity::Binding;
+#[cfg(feature = "rayon")]
+use crate::backend::Rayon;
+use crate::backend::{BackendKind, ParallelBackend, Serial, ThreadsChannels};
+use crate::preflight::ClassTable;
+use crate::usage::{ResourceUsage, UsageSampler};
+use crate::verification::Verification;
+
+
+
+// coefficients of the exact solution polynomial (ce(m, n) in the original)
+const CE: [[f64; 13]; 5] =
+[
+    [ 2.0, 0.0, 0.0, 4.0, 5.0, 3.0, 0.5, 0.02, 0.01, 0.03, 0.5, 0.4, 0.3 ],
+    [ 1.0, 0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 0.01, 0.03, 0.02, 0.4, 0.3, 0.5 ],
+    [ 2.0, 2.0, 0.0, 0.0, 0.0, 2.0, 3.0, 0.04, 0.03, 0.05, 0.3, 0.5, 0.4 ],
+    [ 2.0, 2.0, 0.0, 0.0, 0.0, 2.0, 3.0, 0.03, 0.05, 0.04, 0.2, 0.1, 0.3 ],
+    [ 5.0, 4.0, 3.0, 2.0, 0.1, 0.4, 0.3, 0.05, 0.04, 0.03, 0.1, 0.3, 0.2 ],
+];
+
+// timers (only used when Config::use_timers is set)
+pub const T_TOTAL:  usize = 0;
+pub const T_RHS:    usize = 1;
+pub const T_TXINVR: usize = 2;
+pub const T_XSOLVE: usize = 3;
+pub const T_YSOLVE: usize = 4;
+pub const T_ZSOLVE: usize = 5;
+pub const T_ADD:    usize = 6;
+pub const T_LAST:   usize = 7;
+
+
+
+/* constants that depend on the direction (xi, eta or zeta) of the flux
+ * differences and of the factorizations, so that those only need to be
+ * written once (tx2/ty2/tz2, dx1tx1..dz5tz1, xxcon1..zzcon5, and so on)
+ */
+#[derive(Clone, Copy)]
+struct Direction
+{
+    t2:     f64,
+    dt1:    [f64; 5], // d?1t?1 ... d?5t?1
+    con:    [f64; 5], // ??con1 ... ??con5
+    dtt1:   f64,
+    dtt2:   f64,
+    c2dtt1: f64,
+    dn:     f64, // dx2, dy3 or dz4 (diffusion of the normal momentum)
+    d5:     f64,
+    dmax:   f64,
+    d1:     f64,
+}
+
+// the factors and the right hand side of the systems of a grid line
+struct LineSystem
+{
+    cv:   Vec<f64>,
+    rho:  Vec<f64>,
+    lhs:  Vec<[f64; 5]>,
+    lhsp: Vec<[f64; 5]>,
+    lhsm: Vec<[f64; 5]>,
+    rhs:  Vec<[f64; 5]>,
+}
+
+impl LineSystem
+{
+    // the buffers of a line of 'n' points
+    fn new( n: &usize ) -> LineSystem
+    {
+        return LineSystem
+        {
+            cv:   vec![0.0; *n],
+            rho:  vec![0.0; *n],
+            lhs:  vec![[0.0; 5]; *n],
+            lhsp: vec![[0.0; 5]; *n],
+            lhsm: vec![[0.0; 5]; *n],
+            rhs:  vec![[0.0; 5]; *n],
+        };
+    }
+}
+
+
+
+struct SP
+{
+    grid_points: [usize; 3],
+    dt:          f64,
+    dnm1:        [f64; 3], // dnxm1, dnym1, dnzm1
+    c1:          f64,
+    c2:          f64,
+    c1c2:        f64,
+    c1c5:        f64,
+    c3c4:        f64,
+    bt:          f64,
+    dssp:        f64,
+    comz1:       f64,
+    comz4:       f64,
+    comz5:       f64,
+    comz6:       f64,
+    c2iv:        f64,
+    con43:       f64,
+    dirs:        [Direction; 3],
+
+    u:           Vec<[f64; 5]>,
+    rhs:         Vec<[f64; 5]>,
+    forcing:     Vec<[f64; 5]>,
+    us:          Vec<f64>,
+    vs:          Vec<f64>,
+    ws:          Vec<f64>,
+    qs:          Vec<f64>,
+    rho_i:       Vec<f64>,
+    speed:       Vec<f64>,
+    square:      Vec<f64>,
+
+    use_timers:  bool,
+    timers:      [u128; T_LAST],
+}
+
+
+
+impl SP
+{
+    fn new( grid_points: &[usize; 3], dt: &f64, use_timers: &bool ) -> SP
+    {
+        /* this is "set_constants" from the original code */
+
+        let (c1, c2, c3, c4, c5): (f64, f64, f64, f64, f64) = (1.4, 0.4, 0.1, 1.0, 1.4);
+        let (c1c2, c1c5, c3c4): (f64, f64, f64) = (c1 * c2, c1 * c5, c3 * c4);
+        let conz1: f64 = 1.0 - c1c5;
+        let (con43, con16): (f64, f64) = (4.0 / 3.0, 1.0 / 6.0);
+
+        let dnm1: [f64; 3] = [1.0 / ((grid_points[0] - 1) as f64),
+                              1.0 / ((grid_points[1] - 1) as f64),
+                              1.0 / ((grid_points[2] - 1) as f64)];
+
+        let dx: [f64; 5] = [0.75; 5];
+        let dy: [f64; 5] = [0.75; 5];
+        let dz: [f64; 5] = [1.0; 5];
+        let dssp: f64 = 0.25 * f64::max(dx[0], f64::max(dy[0], dz[0]));
+        let dtdssp: f64 = dt * dssp;
+
+        let direction = |d: &[f64; 5], dn: f64, dmax: f64, dnm1: f64| -> Direction
+        {
+            let t1: f64 = 1.0 / (dnm1 * dnm1);
+            let t2: f64 = 1.0 / (2.0 * dnm1);
+            let t3: f64 = 1.0 / dnm1;
+            let c3c4t3: f64 = c3c4 * t3;
+            Direction
+            {
+                t2,
+                dt1:    [d[0] * t1, d[1] * t1, d[2] * t1, d[3] * t1, d[4] * t1],
+                con:    [c3c4t3 * con43 * t3, c3c4t3 * t3, c3c4t3 * conz1 * t3,
+                         c3c4t3 * con16 * t3, c3c4t3 * c1c5 * t3],
+                dtt1:   dt * t1,
+                dtt2:   dt * t2,
+                c2dtt1: 2.0 * (dt * t1),
+                dn,
+                d5:     d[4],
+                dmax,
+                d1:     d[0],
+            }
+        };
+
+        let points: usize = grid_points[0] * grid_points[1] * grid_points[2];
+
+        return SP
+        {
+            grid_points: *grid_points,
+            dt: *dt,
+            dnm1,
+            c1,
+            c2,
+            c1c2,
+            c1c5,
+            c3c4,
+            bt: f64::sqrt(0.5),
+            dssp,
+            comz1: dtdssp,
+            comz4: 4.0 * dtdssp,
+            comz5: 5.0 * dtdssp,
+            comz6: 6.0 * dtdssp,
+            c2iv: 2.5,
+            con43,
+            dirs: [direction(&dx, dx[1], f64::max(dx[2], dx[3]), dnm1[0]),
+                   direction(&dy, dy[2], f64::max(dy[1], dy[3]), dnm1[1]),
+                   direction(&dz, dz[3], f64::max(dz[1], dz[2]), dnm1[2])],
+
+            u:       vec![[0.0; 5]; points],
+            rhs:     vec![[0.0; 5]; points],
+            forcing: vec![[0.0; 5]; points],
+            us:      vec![0.0; points],
+            vs:      vec![0.0; points],
+            ws:      vec![0.0; points],
+            qs:      vec![0.0; points],
+            rho_i:   vec![0.0; points],
+            speed:   vec![0.0; points],
+            square:  vec![0.0; points],
+
+            use_timers: *use_timers,
+            timers: [0; T_LAST],
+        };
+    }
+
+    // index of the grid point (i, j, k)
+    fn at( &self, i: &usize, j: &usize, k: &usize ) -> usize
+    {
+        return ((k * self.grid_points[1]) + j) * self.grid_points[0] + i;
+    }
+
+    /* returns the index of the first point, the stride and the length of the
+     * grid line along direction 'd' that crosses the other two directions at
+     * 'a' and 'b' (in the usual i, j, k order)
+     */
+    fn line( &self, d: &usize, a: &usize, b: &usize ) -> (usize, usize, usize)
+    {
+        let (nx, ny): (usize, usize) = (self.grid_points[0], self.grid_points[1]);
+        return match *d
+        {
+            0 => (self.at(&0, a, b), 1, nx),
+            1 => (self.at(a, &0, b), nx, ny),
+            _ => (self.at(a, b, &0), nx * ny, self.grid_points[2]),
+        };
+    }
+
+    // (i, j, k) coordinates of position 'p' of the grid line described above
+    fn line_point( d: &usize, p: &usize, a: &usize, b: &usize ) -> [usize; 3]
+    {
+        return match *d { 0 => [*p, *a, *b], 1 => [*a, *p, *b], _ => [*a, *b, *p], };
+    }
+
+    // the two directions other than 'd'
+    fn across( &self, d: &usize ) -> (usize, usize)
+    {
+        return match *d
+        {
+            0 => (self.grid_points[1], self.grid_points[2]),
+            1 => (self.grid_points[0], self.grid_points[2]),
+            _ => (self.grid_points[0], self.grid_points[1]),
+        };
+    }
+
+    fn start_timer( &self ) -> Instant
+    {
+        return Instant::now();
+    }
+
+    fn stop_timer( &mut self, timer: &usize, moment: &Instant )
+    {
+        if self.use_timers { self.timers[*timer] += moment.elapsed().as_micros(); }
+    }
+
+	/* ********************************************************************* */
+
+    // the exact solution at (xi, eta, zeta)
+    fn exact_solution( xi: &f64, eta: &f64, zeta: &f64, dtemp: &mut [f64; 5] )
+    {
+        for m in 0usize..5
+        {
+            dtemp[m] = CE[m][0] +
+                       xi * (CE[m][1] + xi * (CE[m][4] + xi * (CE[m][7] + xi * CE[m][10]))) +
+                       eta * (CE[m][2] + eta * (CE[m][5] + eta * (CE[m][8] + eta * CE[m][11]))) +
+                       zeta * (CE[m][3] + zeta * (CE[m][6] + zeta * (CE[m][9] + zeta * CE[m][12])));
+        }
+    }
+
+    // fourth order dissipation at position 'p' of a line of 'n' values, v(q) being the q-th
+    fn dissipation<V: Fn(usize) -> f64>( p: &usize, n: &usize, v: V ) -> f64
+    {
+        let p: usize = *p;
+        return if p == 1 { 5.0 * v(p) - 4.0 * v(p + 1) + v(p + 2) }
+               else if p == 2 { -4.0 * v(p - 1) + 6.0 * v(p) - 4.0 * v(p + 1) + v(p + 2) }
+               else if p == (n - 3) { v(p - 2) - 4.0 * v(p - 1) + 6.0 * v(p) - 4.0 * v(p + 1) }
+               else if p == (n - 2) { v(p - 2) - 4.0 * v(p - 1) + 5.0 * v(p) }
+               else { v(p - 2) - 4.0 * v(p - 1) + 6.0 * v(p) - 4.0 * v(p + 1) + v(p + 2) };
+    }
+
+    /* calls 'f' with the index and the value in 'field' (one per grid point)
+     * of every interior point, the planes being spread over the threads of
+     * 'backend'
+     */
+    fn for_interior<B, T, F>( grid_points: &[usize; 3], backend: &B, field: &mut [T], f: F )
+        where B: ParallelBackend, T: Send, F: Fn(usize, &mut T) + Sync
+    {
+        let [nx, ny, nz] = *grid_points;
+        let plane: usize = nx * ny;
+
+        backend.parallel_for_chunks(field, &plane, |k, values|
+        {
+            if (k == 0) || (k == (nz - 1)) { return; }
+            for j in 1usize..(ny - 1)
+            {
+                for i in 1usize..(nx - 1)
+                {
+                    let q: usize = (j * nx) + i;
+                    f((k * plane) + q, &mut values[q]);
+                }
+            }
+        });
+    }
+
+    /* this function sets the initial values of u by a transfinite
+     * interpolation of the exact solution on the faces of the domain, and
+     * then sets the exact values on the boundaries
+     */
+    fn initialize( &mut self )
+    {
+        let [nx, ny, nz] = self.grid_points;
+        let mut temp: [f64; 5] = [0.0; 5];
+        let mut Pface: [[[f64; 5]; 3]; 2] = [[[0.0; 5]; 3]; 2];
+
+        // first store the "interpolated" values everywhere on the grid
+        for k in 0usize..nz
+        {
+            let zeta: f64 = (k as f64) * self.dnm1[2];
+            for j in 0usize..ny
+            {
+                let eta: f64 = (j as f64) * self.dnm1[1];
+                for i in 0usize..nx
+                {
+                    let xi: f64 = (i as f64) * self.dnm1[0];
+
+                    for (f, face) in Pface.iter_mut().enumerate()
+                    {
+                        let P: f64 = f as f64;
+                        SP::exact_solution(&P, &eta, &zeta, &mut face[0]);
+                        SP::exact_solution(&xi, &P, &zeta, &mut face[1]);
+                        SP::exact_solution(&xi, &eta, &P, &mut face[2]);
+                    }
+
+                    let point: usize = self.at(&i, &j, &k);
+                    for (m, u_m) in self.u[point].iter_mut().enumerate()
+                    {
+                        let Pxi: f64   = xi * Pface[1][0][m] + (1.0 - xi) * Pface[0][0][m];
+                        let Peta: f64  = eta * Pface[1][1][m] + (1.0 - eta) * Pface[0][1][m];
+                        let Pzeta: f64 = zeta * Pface[1][2][m] + (1.0 - zeta) * Pface[0][2][m];
+
+                        *u_m = Pxi + Peta + Pzeta -
+                               Pxi * Peta - Pxi * Pzeta - Peta * Pzeta +
+                               Pxi * Peta * Pzeta;
+                    }
+                }
+            }
+        }
+
+        /* now store the exact values on the boundaries (west and east faces,
+         * then south and north, then bottom and top, which take precedence
+         * on the edges)
+         */
+        for d in 0usize..3
+        {
+            let (na, nb): (usize, usize) = self.across(&d);
+            for (p, P) in [(0usize, 0.0f64), (self.grid_points[d] - 1, 1.0f64)]
+            {
+                for b in 0usize..nb
+                {
+                    for a in 0usize..na
+                    {
+                        let ijk: [usize; 3] = SP::line_point(&d, &p, &a, &b);
+                        let mut coordinates: [f64; 3] = [(ijk[0] as f64) * self.dnm1[0],
+                                                         (ijk[1] as f64) * self.dnm1[1],
+                                                         (ijk[2] as f64) * self.dnm1[2]];
+                        coordinates[d] = P;
+                        SP::exact_solution(&coordinates[0], &coordinates[1], &coordinates[2],
+                                           &mut temp);
+                        let point: usize = self.at(&ijk[0], &ijk[1], &ijk[2]);
+                        self.u[point] = temp;
+                    }
+                }
+            }
+        }
+    }
+
+    // computes the forcing term, based on the exact solution
+    fn exact_rhs( &mut self )
+    {
+        let (c1, c2, dssp): (f64, f64, f64) = (self.c1, self.c2, self.dssp);
+        let mut dtemp: [f64; 5] = [0.0; 5];
+        let max_line: usize = *self.grid_points.iter().max().unwrap();
+        let mut ue: Vec<[f64; 5]> = vec![[0.0; 5]; max_line];
+        let mut buf: Vec<[f64; 5]> = vec![[0.0; 5]; max_line];
+        let mut cuf: Vec<f64> = vec![0.0; max_line];
+        let mut q: Vec<f64> = vec![0.0; max_line];
+        let mut column: Vec<f64> = vec![0.0; max_line];
+
+        for f in self.forcing.iter_mut() { *f = [0.0; 5]; }
+
+        // xi-, eta- and zeta-direction flux differences
+        for d in 0usize..3
+        {
+            let dir: Direction = self.dirs[d];
+            let n: usize = d + 1; // the momentum component normal to the direction
+            let (na, nb): (usize, usize) = self.across(&d);
+
+            for b in 1usize..(nb - 1)
+            {
+                for a in 1usize..(na - 1)
+                {
+                    let (first, stride, len): (usize, usize, usize) = self.line(&d, &a, &b);
+
+                    for p in 0usize..len
+                    {
+                        let ijk: [usize; 3] = SP::line_point(&d, &p, &a, &b);
+                        let xi: f64   = (ijk[0] as f64) * self.dnm1[0];
+                        let eta: f64  = (ijk[1] as f64) * self.dnm1[1];
+                        let zeta: f64 = (ijk[2] as f64) * self.dnm1[2];
+
+                        SP::exact_solution(&xi, &eta, &zeta, &mut dtemp);
+                        ue[p] = dtemp;
+
+                        let dtpp: f64 = 1.0 / dtemp[0];
+                        for m in 1usize..5 { buf[p][m] = dtpp * dtemp[m]; }
+
+                        cuf[p] = buf[p][n] * buf[p][n];
+                        buf[p][0] = buf[p][1] * buf[p][1] + buf[p][2] * buf[p][2] +
+                                    buf[p][3] * buf[p][3];
+                        q[p] = 0.5 * (buf[p][1] * ue[p][1] + buf[p][2] * ue[p][2] +
+                                      buf[p][3] * ue[p][3]);
+                    }
+
+                    for p in 1usize..(len - 1)
+                    {
+                        let (pm, pp): (usize, usize) = (p - 1, p + 1);
+                        let f: &mut [f64; 5] = &mut self.forcing[first + (p * stride)];
+
+                        f[0] = f[0] - dir.t2 * (ue[pp][n] - ue[pm][n]) +
+                               dir.dt1[0] * (ue[pp][0] - 2.0 * ue[p][0] + ue[pm][0]);
+
+                        for m in 1usize..4
+                        {
+                            if m == n
+                            {
+                                f[m] = f[m] - dir.t2 * (
+                                           (ue[pp][m] * buf[pp][n] + c2 * (ue[pp][4] - q[pp])) -
+                                           (ue[pm][m] * buf[pm][n] + c2 * (ue[pm][4] - q[pm]))) +
+                                       dir.con[0] * (buf[pp][m] - 2.0 * buf[p][m] + buf[pm][m]) +
+                                       dir.dt1[m] * (ue[pp][m] - 2.0 * ue[p][m] + ue[pm][m]);
+                            }
+                            else
+                            {
+                                f[m] = f[m] - dir.t2 * (
+                                           ue[pp][m] * buf[pp][n] - ue[pm][m] * buf[pm][n]) +
+                                       dir.con[1] * (buf[pp][m] - 2.0 * buf[p][m] + buf[pm][m]) +
+                                       dir.dt1[m] * (ue[pp][m] - 2.0 * ue[p][m] + ue[pm][m]);
+                            }
+                        }
+
+                        f[4] = f[4] - dir.t2 * (
+                                   buf[pp][n] * (c1 * ue[pp][4] - c2 * q[pp]) -
+                                   buf[pm][n] * (c1 * ue[pm][4] - c2 * q[pm])) +
+                               0.5 * dir.con[2] * (buf[pp][0] - 2.0 * buf[p][0] + buf[pm][0]) +
+                               dir.con[3] * (cuf[pp] - 2.0 * cuf[p] + cuf[pm]) +
+                               dir.con[4] * (buf[pp][4] - 2.0 * buf[p][4] + buf[pm][4]) +
+                               dir.dt1[4] * (ue[pp][4] - 2.0 * ue[p][4] + ue[pm][4]);
+                    }
+
+                    // fourth-order dissipation
+                    for m in 0usize..5
+                    {
+                        for (column_p, ue_p) in column.iter_mut().zip(ue.iter()).take(len)
+                        {
+                            *column_p = ue_p[m];
+                        }
+                        for p in 1usize..(len - 1)
+                        {
+                            let point: usize = first + (p * stride);
+                            self.forcing[point][m] -=
+                                    dssp * SP::dissipation(&p, &len, |q| column[q]);
+                        }
+                    }
+                }
+            }
+        }
+
+        // now change the sign of the forcing function
+        let [nx, ny, nz] = self.grid_points;
+        for k in 1usize..(nz - 1)
+        {
+            for j in 1usize..(ny - 1)
+            {
+                for i in 1usize..(nx - 1)
+                {
+                    let point: usize = self.at(&i, &j, &k);
+                    for m in 0usize..5 { self.forcing[point][m] = -self.forcing[point][m]; }
+                }
+            }
+        }
+    }
+
+	/* ********************************************************************* */
+
+    fn compute_rhs<B: ParallelBackend>( &mut self, backend: &B )
+    {
+        let moment: Instant = self.start_timer();
+        let (c1, c2, c1c2, dssp, con43, dt): (f64, f64, f64, f64, f64, f64) =
+                (self.c1, self.c2, self.c1c2, self.dssp, self.con43, self.dt);
+
+        /* compute the reciprocal of density, and the kinetic energy,
+         * and the speed of sound
+         */
+        for point in 0usize..self.u.len()
+        {
+            let u: [f64; 5] = self.u[point];
+            let rho_inv: f64 = 1.0 / u[0];
+            self.rho_i[point] = rho_inv;
+            self.us[point] = u[1] * rho_inv;
+            self.vs[point] = u[2] * rho_inv;
+            self.ws[point] = u[3] * rho_inv;
+            self.square[point] = 0.5 * (u[1] * u[1] + u[2] * u[2] + u[3] * u[3]) * rho_inv;
+            self.qs[point] = self.square[point] * rho_inv;
+
+            // (don't need speed and ainx until the lhs computation)
+            let aux: f64 = c1c2 * rho_inv * (u[4] - self.square[point]);
+            self.speed[point] = aux.sqrt();
+        }
+
+        /* copy the exact forcing term to the right hand side; because
+         * this forcing term is known, we can store it on the whole grid
+         * including the boundary
+         */
+        self.rhs.copy_from_slice(&self.forcing);
+
+        /* compute xi-, eta- and zeta-direction fluxes, and scale them by the
+         * time step; a point only changes its own right hand side
+         */
+        let [nx, ny, _] = self.grid_points;
+        let strides: [usize; 3] = [1, nx, nx * ny];
+        let mut rhs: Vec<[f64; 5]> = std::mem::take(&mut self.rhs);
+        let sp: &SP = self;
+        let vel: [&Vec<f64>; 3] = [&sp.us, &sp.vs, &sp.ws];
+
+        SP::for_interior(&sp.grid_points, backend, &mut rhs, |point, r|
+        {
+            let ijk: [usize; 3] = [point % nx, (point / nx) % ny, point / (nx * ny)];
+
+            for d in 0usize..3
+            {
+                let dir: Direction = sp.dirs[d];
+                let n: usize = d + 1; // the momentum component normal to the direction
+                let (stride, len, p): (usize, usize, usize) =
+                        (strides[d], sp.grid_points[d], ijk[d]);
+                let (pm, pp): (usize, usize) = (point - stride, point + stride);
+                let (u, up, um): ([f64; 5], [f64; 5], [f64; 5]) = (sp.u[point], sp.u[pp], sp.u[pm]);
+                let (vijk, vp1, vm1): (f64, f64, f64) = (vel[d][point], vel[d][pp], vel[d][pm]);
+
+                r[0] = r[0] + dir.dt1[0] * (up[0] - 2.0 * u[0] + um[0]) -
+                       dir.t2 * (up[n] - um[n]);
+
+                for m in 1usize..4
+                {
+                    if m == n
+                    {
+                        r[m] = r[m] + dir.dt1[m] * (up[m] - 2.0 * u[m] + um[m]) +
+                               dir.con[1] * con43 * (vp1 - 2.0 * vijk + vm1) -
+                               dir.t2 * (up[m] * vp1 - um[m] * vm1 +
+                                         (up[4] - sp.square[pp] - um[4] + sp.square[pm]) * c2);
+                    }
+                    else
+                    {
+                        let v: &Vec<f64> = vel[m - 1];
+                        r[m] = r[m] + dir.dt1[m] * (up[m] - 2.0 * u[m] + um[m]) +
+                               dir.con[1] * (v[pp] - 2.0 * v[point] + v[pm]) -
+                               dir.t2 * (up[m] * vp1 - um[m] * vm1);
+                    }
+                }
+
+                r[4] = r[4] + dir.dt1[4] * (up[4] - 2.0 * u[4] + um[4]) +
+                       dir.con[2] * (sp.qs[pp] - 2.0 * sp.qs[point] + sp.qs[pm]) +
+                       dir.con[3] * (vp1 * vp1 - 2.0 * vijk * vijk + vm1 * vm1) +
+                       dir.con[4] * (up[4] * sp.rho_i[pp] - 2.0 * u[4] * sp.rho_i[point] +
+                                     um[4] * sp.rho_i[pm]) -
+                       dir.t2 * ((c1 * up[4] - c2 * sp.square[pp]) * vp1 -
+                                 (c1 * um[4] - c2 * sp.square[pm]) * vm1);
+
+                // add fourth order dissipation
+                let first: usize = point - (p * stride);
+                for (m, r_m) in r.iter_mut().enumerate()
+                {
+                    *r_m -= dssp * SP::dissipation(&p, &len, |q| sp.u[first + (q * stride)][m]);
+                }
+            }
+
+            for r_m in r.iter_mut() { *r_m *= dt; }
+        });
+        self.rhs = rhs;
+
+        self.stop_timer(&T_RHS, &moment);
+    }
+
+    // block-diagonal matrix-vector multiplication
+    fn txinvr<B: ParallelBackend>( &mut self, backend: &B )
+    {
+        let moment: Instant = self.start_timer();
+        let (c2, bt): (f64, f64) = (self.c2, self.bt);
+        let mut rhs: Vec<[f64; 5]> = std::mem::take(&mut self.rhs);
+        let sp: &SP = self;
+
+        SP::for_interior(&sp.grid_points, backend, &mut rhs, |point, r|
+        {
+            let ru1: f64 = sp.rho_i[point];
+            let (uu, vv, ww): (f64, f64, f64) = (sp.us[point], sp.vs[point], sp.ws[point]);
+            let ac: f64 = sp.speed[point];
+            let ac2inv: f64 = ac * ac;
+
+            let [r1, r2, r3, r4, r5] = *r;
+
+            let t1: f64 = c2 / ac2inv * (sp.qs[point] * r1 - uu * r2 - vv * r3 - ww * r4 + r5);
+            let t2: f64 = bt * ru1 * (uu * r1 - r2);
+            let t3: f64 = (bt * ru1 * ac) * t1;
+
+            *r = [r1 - t1, -ru1 * (ww * r1 - r4), ru1 * (vv * r1 - r3), -t2 + t3, t2 + t3];
+        });
+        self.rhs = rhs;
+
+        self.stop_timer(&T_TXINVR, &moment);
+    }
+
+    // block-diagonal matrix-vector multiplication (after the x-sweep)
+    fn ninvr<B: ParallelBackend>( &mut self, backend: &B )
+    {
+        let bt: f64 = self.bt;
+
+        SP::for_interior(&self.grid_points, backend, &mut self.rhs, |_, r|
+        {
+            let [r1, r2, r3, r4, r5] = *r;
+
+            let t1: f64 = bt * r3;
+            let t2: f64 = 0.5 * (r4 + r5);
+
+            *r = [-r2, r1, bt * (r4 - r5), -t1 + t2, t1 + t2];
+        });
+    }
+
+    // block-diagonal matrix-vector multiplication (after the y-sweep)
+    fn pinvr<B: ParallelBackend>( &mut self, backend: &B )
+    {
+        let bt: f64 = self.bt;
+
+        SP::for_interior(&self.grid_points, backend, &mut self.rhs, |_, r|
+        {
+            let [r1, r2, r3, r4, r5] = *r;
+
+            let t1: f64 = bt * r1;
+            let t2: f64 = 0.5 * (r4 + r5);
+
+            *r = [bt * (r4 - r5), -r3, r2, -t1 + t2, t1 + t2];
+        });
+    }
+
+    // block-diagonal matrix-vector multiplication (after the z-sweep)
+    fn tzetar<B: ParallelBackend>( &mut self, backend: &B )
+    {
+        let (bt, c2iv): (f64, f64) = (self.bt, self.c2iv);
+        let mut rhs: Vec<[f64; 5]> = std::mem::take(&mut self.rhs);
+        let sp: &SP = self;
+
+        SP::for_interior(&sp.grid_points, backend, &mut rhs, |point, r|
+        {
+            let (xvel, yvel, zvel): (f64, f64, f64) = (sp.us[point], sp.vs[point], sp.ws[point]);
+            let ac: f64 = sp.speed[point];
+            let ac2u: f64 = ac * ac;
+
+            let [r1, r2, r3, r4, r5] = *r;
+
+            let uzik1: f64 = sp.u[point][0];
+            let btuz: f64 = bt * uzik1;
+
+            let t1: f64 = btuz / ac * (r4 + r5);
+            let t2: f64 = r3 + t1;
+            let t3: f64 = btuz * (r4 - r5);
+
+            *r = [t2,
+                  -uzik1 * r2 + xvel * t2,
+                  uzik1 * r1 + yvel * t2,
+                  zvel * t2 + t3,
+                  uzik1 * (-xvel * r2 + yvel * r1) + sp.qs[point] * t2 + c2iv * ac2u * t1 +
+                          zvel * t3];
+        });
+        self.rhs = rhs;
+    }
+
+	/* ********************************************************************* */
+    /* solves one line of the three scalar penta-diagonal systems by the
+     * Thomas algorithm; lhs holds the factors for the first three
+     * components (the u-eigenvalue), lhsp and lhsm the ones for the u+c and
+     * u-c factors, and rhs is replaced by the solution
+     */
+    #[allow(clippy::needless_range_loop)] // the rows i, i + 1 and i + 2 are updated together
+    fn penta_solve( lhs: &mut [[f64; 5]], lhsp: &mut [[f64; 5]], lhsm: &mut [[f64; 5]],
+                    rhs: &mut [[f64; 5]] )
+    {
+        let n: usize = rhs.len();
+        let (mut fac1, mut i, mut i1, mut i2): (f64, usize, usize, usize);
+
+        // FORWARD ELIMINATION
+        for i in 0usize..(n - 2)
+        {
+            i1 = i + 1;
+            i2 = i + 2;
+            fac1 = 1.0 / lhs[i][2];
+            lhs[i][3] *= fac1;
+            lhs[i][4] *= fac1;
+            for m in 0usize..3 { rhs[i][m] *= fac1; }
+            lhs[i1][2] -= lhs[i1][1] * lhs[i][3];
+            lhs[i1][3] -= lhs[i1][1] * lhs[i][4];
+            for m in 0usize..3 { rhs[i1][m] -= lhs[i1][1] * rhs[i][m]; }
+            lhs[i2][1] -= lhs[i2][0] * lhs[i][3];
+            lhs[i2][2] -= lhs[i2][0] * lhs[i][4];
+            for m in 0usize..3 { rhs[i2][m] -= lhs[i2][0] * rhs[i][m]; }
+        }
+
+        /* the last two rows in this grid block are a bit different,
+         * since they do not have two more rows available for the
+         * elimination of off-diagonal entries
+         */
+        i = n - 2;
+        i1 = n - 1;
+        fac1 = 1.0 / lhs[i][2];
+        lhs[i][3] *= fac1;
+        lhs[i][4] *= fac1;
+        for m in 0usize..3 { rhs[i][m] *= fac1; }
+        lhs[i1][2] -= lhs[i1][1] * lhs[i][3];
+        lhs[i1][3] -= lhs[i1][1] * lhs[i][4];
+        for m in 0usize..3 { rhs[i1][m] -= lhs[i1][1] * rhs[i][m]; }
+
+        // scale the last row immediately
+        let fac2: f64 = 1.0 / lhs[i1][2];
+        for m in 0usize..3 { rhs[i1][m] *= fac2; }
+
+        // do the u+c and the u-c factors
+        for (m, lhs) in [(3usize, &mut *lhsp), (4usize, &mut *lhsm)]
+        {
+            for i in 0usize..(n - 2)
+            {
+                i1 = i + 1;
+                i2 = i + 2;
+                fac1 = 1.0 / lhs[i][2];
+                lhs[i][3] *= fac1;
+                lhs[i][4] *= fac1;
+                rhs[i][m] *= fac1;
+                lhs[i1][2] -= lhs[i1][1] * lhs[i][3];
+                lhs[i1][3] -= lhs[i1][1] * lhs[i][4];
+                rhs[i1][m] -= lhs[i1][1] * rhs[i][m];
+                lhs[i2][1] -= lhs[i2][0] * lhs[i][3];
+                lhs[i2][2] -= lhs[i2][0] * lhs[i][4];
+                rhs[i2][m] -= lhs[i2][0] * rhs[i][m];
+            }
+
+            // and again the last two rows separately
+            i = n - 2;
+            i1 = n - 1;
+            fac1 = 1.0 / lhs[i][2];
+            lhs[i][3] *= fac1;
+            lhs[i][4] *= fac1;
+            rhs[i][m] *= fac1;
+            lhs[i1][2] -= lhs[i1][1] * lhs[i][3];
+            lhs[i1][3] -= lhs[i1][1] * lhs[i][4];
+            rhs[i1][m] -= lhs[i1][1] * rhs[i][m];
+
+            // scale the last row immediately
+            rhs[i1][m] /= lhs[i1][2];
+        }
+
+        // BACKSUBSTITUTION
+        i = n - 2;
+        i1 = n - 1;
+        for m in 0usize..3 { rhs[i][m] -= lhs[i][3] * rhs[i1][m]; }
+        rhs[i][3] -= lhsp[i][3] * rhs[i1][3];
+        rhs[i][4] -= lhsm[i][3] * rhs[i1][4];
+
+        for i in (0usize..(n - 2)).rev()
+        {
+            i1 = i + 1;
+            i2 = i + 2;
+
+            // the first three factors
+            for m in 0usize..3
+            {
+                rhs[i][m] = rhs[i][m] - lhs[i][3] * rhs[i1][m] - lhs[i][4] * rhs[i2][m];
+            }
+
+            // and the remaining two
+            rhs[i][3] = rhs[i][3] - lhsp[i][3] * rhs[i1][3] - lhsp[i][4] * rhs[i2][3];
+            rhs[i][4] = rhs[i][4] - lhsm[i][3] * rhs[i1][4] - lhsm[i][4] * rhs[i2][4];
+        }
+    }
+
+    /* builds the left hand sides of the line along direction 'd' that starts
+     * at the point 'first' (its points 'stride' apart), and solves them for
+     * 'line.rhs'
+     */
+    fn solve_line( &self, d: &usize, first: &usize, stride: &usize, line: &mut LineSystem )
+    {
+        let dir: Direction = self.dirs[*d];
+        let (comz1, comz4, comz5, comz6): (f64, f64, f64, f64) =
+                (self.comz1, self.comz4, self.comz5, self.comz6);
+        let (c3c4, c1c5, con43): (f64, f64, f64) = (self.c3c4, self.c1c5, self.con43);
+        let n: usize = self.grid_points[*d];
+        let vel: &Vec<f64> = match *d { 0 => &self.us, 1 => &self.vs, _ => &self.ws, };
+        let LineSystem { cv, rho, lhs, lhsp, lhsm, rhs } = line;
+
+        /* computes the left hand side for the three factors,
+         * first filling it for the u-eigenvalue
+         */
+        for p in 0usize..n
+        {
+            let point: usize = first + (p * stride);
+            let ru1: f64 = c3c4 * self.rho_i[point];
+            cv[p] = vel[point];
+            rho[p] = f64::max(f64::max(dir.dn + con43 * ru1, dir.d5 + c1c5 * ru1),
+                              f64::max(dir.dmax + ru1, dir.d1));
+        }
+
+        lhs[0] = [0.0, 0.0, 1.0, 0.0, 0.0];
+        lhs[n - 1] = [0.0, 0.0, 1.0, 0.0, 0.0];
+        for p in 1usize..(n - 1)
+        {
+            lhs[p] = [0.0,
+                      -dir.dtt2 * cv[p - 1] - dir.dtt1 * rho[p - 1],
+                      1.0 + dir.c2dtt1 * rho[p],
+                      dir.dtt2 * cv[p + 1] - dir.dtt1 * rho[p + 1],
+                      0.0];
+        }
+
+        // add fourth order dissipation
+        lhs[1][2] += comz5;
+        lhs[1][3] -= comz4;
+        lhs[1][4] += comz1;
+
+        lhs[2][1] -= comz4;
+        lhs[2][2] += comz6;
+        lhs[2][3] -= comz4;
+        lhs[2][4] += comz1;
+
+        for row in lhs[3..(n - 3)].iter_mut()
+        {
+            row[0] += comz1;
+            row[1] -= comz4;
+            row[2] += comz6;
+            row[3] -= comz4;
+            row[4] += comz1;
+        }
+
+        let p: usize = n - 3;
+        lhs[p][0] += comz1;
+        lhs[p][1] -= comz4;
+        lhs[p][2] += comz6;
+        lhs[p][3] -= comz4;
+
+        lhs[p + 1][0] += comz1;
+        lhs[p + 1][1] -= comz4;
+        lhs[p + 1][2] += comz5;
+
+        /* subsequently, fill the other factors (u+c), (u-c)
+         * by adding to the first
+         */
+        lhsp[0] = lhs[0];
+        lhsm[0] = lhs[0];
+        lhsp[n - 1] = lhs[n - 1];
+        lhsm[n - 1] = lhs[n - 1];
+        for p in 1usize..(n - 1)
+        {
+            let (sm, sp): (f64, f64) = (self.speed[first + ((p - 1) * stride)],
+                                        self.speed[first + ((p + 1) * stride)]);
+            lhsp[p] = [lhs[p][0], lhs[p][1] - dir.dtt2 * sm, lhs[p][2],
+                       lhs[p][3] + dir.dtt2 * sp, lhs[p][4]];
+            lhsm[p] = [lhs[p][0], lhs[p][1] + dir.dtt2 * sm, lhs[p][2],
+                       lhs[p][3] - dir.dtt2 * sp, lhs[p][4]];
+        }
+
+        SP::penta_solve(lhs, lhsp, lhsm, rhs);
+    }
+
+    /* solves all the lines along direction 'd' (the body of x_solve, y_solve
+     * and z_solve)
+     */
+    fn solve_direction<B: ParallelBackend>( &mut self, backend: &B, d: &usize )
+    {
+        let [nx, ny, nz] = self.grid_points;
+        let (na, nb): (usize, usize) = self.across(d);
+        let n: usize = self.grid_points[*d];
+
+        if *d < 2
+        {
+            // the x and y lines of a plane lie in it, so it is solved on its own
+            let plane: usize = nx * ny;
+            let mut rhs: Vec<[f64; 5]> = std::mem::take(&mut self.rhs);
+            let sp: &SP = self;
+
+            backend.parallel_for_chunks(&mut rhs, &plane, |k, values|
+            {
+                if (k == 0) || (k == (nz - 1)) { return; }
+                let mut line: LineSystem = LineSystem::new(&n);
+                for a in 1usize..(na - 1)
+                {
+                    let (first, stride, _): (usize, usize, usize) = sp.line(d, &a, &k);
+                    let start: usize = first - (k * plane);
+                    for (p, rhs_p) in line.rhs.iter_mut().enumerate()
+                    {
+                        *rhs_p = values[start + (p * stride)];
+                    }
+                    sp.solve_line(d, &first, &stride, &mut line);
+                    for (p, rhs_p) in line.rhs.iter().enumerate()
+                    {
+                        values[start + (p * stride)] = *rhs_p;
+                    }
+                }
+            });
+            self.rhs = rhs;
+            return;
+        }
+
+        /* the z lines cross every plane, so the threads solve the lines of
+         * their rows of y into their own buffers, copied back afterwards
+         */
+        let sp: &SP = self;
+        let solved: Vec<[f64; 5]> = backend.map_reduce(&(nb - 2), |rows|
+        {
+            let mut line: LineSystem = LineSystem::new(&n);
+            let mut solved: Vec<[f64; 5]> = Vec::with_capacity(rows.len() * (na - 2) * n);
+            for b in (rows.start + 1)..(rows.end + 1)
+            {
+                for a in 1usize..(na - 1)
+                {
+                    let (first, stride, _): (usize, usize, usize) = sp.line(d, &a, &b);
+                    for (p, rhs_p) in line.rhs.iter_mut().enumerate()
+                    {
+                        *rhs_p = sp.rhs[first + (p * stride)];
+                    }
+                    sp.solve_line(d, &first, &stride, &mut line);
+                    solved.extend_from_slice(&line.rhs);
+                }
+            }
+            return solved;
+        },
+        |mut solved, more| { solved.extend(more); return solved; });
+
+        let mut lines = solved.chunks(n);
+        for b in 1usize..(nb - 1)
+        {
+            for a in 1usize..(na - 1)
+            {
+                let (first, stride, _): (usize, usize, usize) = self.line(d, &a, &b);
+                for (p, rhs_p) in lines.next().unwrap().iter().enumerate()
+                {
+                    self.rhs[first + (p * stride)] = *rhs_p;
+                }
+            }
+        }
+    }
+
+    fn x_solve<B: ParallelBackend>( &mut self, backend: &B )
+    {
+        let moment: Instant = self.start_timer();
+        self.solve_direction(backend, &0);
+        self.ninvr(backend);
+        self.stop_timer(&T_XSOLVE, &moment);
+    }
+
+    fn y_solve<B: ParallelBackend>( &mut self, backend: &B )
+    {
+        let moment: Instant = self.start_timer();
+        self.solve_direction(backend, &1);
+        self.pinvr(backend);
+        self.stop_timer(&T_YSOLVE, &moment);
+    }
+
+    fn z_solve<B: ParallelBackend>( &mut self, backend: &B )
+    {
+        let moment: Instant = self.start_timer();
+        self.solve_direction(backend, &2);
+        self.tzetar(backend);
+        self.stop_timer(&T_ZSOLVE, &moment);
+    }
+
+    // addition of update to the vector u
+    fn add<B: ParallelBackend>( &mut self, backend: &B )
+    {
+        let moment: Instant = self.start_timer();
+        let mut u: Vec<[f64; 5]> = std::mem::take(&mut self.u);
+        let sp: &SP = self;
+
+        SP::for_interior(&sp.grid_points, backend, &mut u, |point, u|
+        {
+            for (u_m, rhs_m) in u.iter_mut().zip(sp.rhs[point].iter()) { *u_m += rhs_m; }
+        });
+        self.u = u;
+
+        self.stop_timer(&T_ADD, &moment);
+    }
+
+    fn adi<B: ParallelBackend>( &mut self, backend: &B )
+    {
+        self.compute_rhs(backend);
+        self.txinvr(backend);
+        self.x_solve(backend);
+        self.y_solve(backend);
+        self.z_solve(backend);
+        self.add(backend);
+    }
+
+	/* ********************************************************************* */
+    // this function computes the norm of the difference between the computed
+    // solution and the exact solution
+    fn error_norm( &self, rms: &mut [f64; 5] )
+    {
+        let [nx, ny, nz] = self.grid_points;
+        let mut u_exact: [f64; 5] = [0.0; 5];
+
+        *rms = [0.0; 5];
+        for k in 0usize..nz
+        {
+            let zeta: f64 = (k as f64) * self.dnm1[2];
+            for j in 0usize..ny
+            {
+                let eta: f64 = (j as f64) * self.dnm1[1];
+                for i in 0usize..nx
+                {
+                    let xi: f64 = (i as f64) * self.dnm1[0];
+                    SP::exact_solution(&xi, &eta, &zeta, &mut u_exact);
+
+                    let point: usize = self.at(&i, &j, &k);
+                    for ((rms_m, u_m), u_exact_m) in rms.iter_mut().zip(self.u[point].iter())
+                                                        .zip(u_exact.iter())
+                    {
+                        let add: f64 = u_m - u_exact_m;
+                        *rms_m += add * add;
+                    }
+                }
+            }
+        }
+
+        for rms_m in rms.iter_mut()
+        {
+            for points in self.grid_points.iter() { *rms_m /= (points - 2) as f64; }
+            *rms_m = rms_m.sqrt();
+        }
+    }
+
+    fn rhs_norm( &self, rms: &mut [f64; 5] )
+    {
+        let [nx, ny, nz] = self.grid_points;
+
+        *rms = [0.0; 5];
+        for k in 1usize..(nz - 1)
+        {
+            for j in 1usize..(ny - 1)
+            {
+                for i in 1usize..(nx - 1)
+                {
+                    let point: usize = self.at(&i, &j, &k);
+                    for (rms_m, add) in rms.iter_mut().zip(self.rhs[point].iter())
+                    {
+                        *rms_m += add * add;
+                    }
+                }
+            }
+        }
+
+        for rms_m in rms.iter_mut()
+        {
+            for points in self.grid_points.iter() { *rms_m /= (points - 2) as f64; }
+            *rms_m = rms_m.sqrt();
+        }
+    }
+}
+
+
+// the grid, the time steps and the time step of class 'class_npb'
+pub fn class_size( class_npb: &char ) -> Option<(usize, usize, f64)>
+{
+    return match class_npb
+    {
+        'S' => Some((12, 100, 0.015)),
+        'W' => Some((36, 400, 0.0015)),
+        'A' => Some((64, 400, 0.0015)),
+        'B' => Some((102, 400, 0.001)),
+        'C' => Some((162, 400, 0.00067)),
+        'D' => Some((408, 500, 0.00030)),
+        'E' => Some((1020, 500, 0.0001)),
+        _ => None,
+    };
+}
+
+/* the reference RMS-norms of the residual (xcrref) and of the solution error
+ * (xceref) of the official NPB for class 'class_npb' (kept with all the
+ * digits sp.f gives them)
+ */
+#[allow(clippy::excessive_precision)]
+pub fn reference_norms( class_npb: &char ) -> Option<([f64; 5], [f64; 5])>
+{
+    return match class_npb
+    {
+        'S' => Some(([2.7470315451339479e-02, 1.0360746705285417e-02, 1.6235745065095532e-02,
+                      1.5840557224455615e-02, 3.4849040609362460e-02],
+                     [2.7289258557377227e-05, 1.0364446640837285e-05, 1.6154798287166471e-05,
+                      1.5750704994480102e-05, 3.4177666183390531e-05])),
+        'W' => Some(([0.1893253733584e-02, 0.1717075447775e-03, 0.2778153350936e-03,
+                      0.2887475409984e-03, 0.3143611161242e-02],
+                     [0.7542088599534e-04, 0.6512852253086e-05, 0.1049092285688e-04,
+                      0.1128838671535e-04, 0.1212845639773e-03])),
+        'A' => Some(([2.4799822399300195e+00, 1.1276337964368832e+00, 1.5028977888770491e+00,
+                      1.4217816211695179e+00, 2.1292113035138280e+00],
+                     [1.0900140297820550e-04, 3.7343951769282091e-05, 5.0092785406541633e-05,
+                      4.7671093939528255e-05, 1.3621613399213001e-04])),
+        'B' => Some(([0.6903293579998e+02, 0.3095134488084e+02, 0.4103336647017e+02,
+                      0.3864769009604e+02, 0.5643482272596e+02],
+                     [0.9810006190188e-02, 0.1022827905670e-02, 0.1720597911692e-02,
+                      0.1694479428231e-02, 0.1847456263981e-01])),
+        'C' => Some(([0.5881691581829e+03, 0.2454417603569e+03, 0.3293829191851e+03,
+                      0.3081924971891e+03, 0.4597223799176e+03],
+                     [0.2598120500183e+00, 0.2590888922315e-01, 0.5132886416320e-01,
+                      0.4806073419454e-01, 0.5483377491301e+00])),
+        'D' => Some(([0.1044696216887e+05, 0.3204427762578e+04, 0.4648680733032e+04,
+                      0.4238923283697e+04, 0.7588412036136e+04],
+                     [0.5089471423669e+01, 0.5323514855894e+00, 0.1187051008971e+01,
+                      0.1083734951938e+01, 0.1164108338568e+02])),
+        'E' => Some(([0.6255387422609e+05, 0.1495317020012e+05, 0.2347595750586e+05,
+                      0.2091099783534e+05, 0.4770412841218e+05],
+                     [0.6742735164909e+02, 0.5390656036938e+01, 0.1680647196477e+02,
+                      0.1536963126457e+02, 0.1575330146156e+03])),
+        _ => None,
+    };
+}
+
+pub const EPSILON: f64 = 1.0e-08;
+
+fn relative_difference( value: &f64, reference: &f64 ) -> f64
+{
+    return ((value - reference) / reference).abs();
+}
+
+pub fn verify( class_npb: &char, xcr: &[f64; 5], xce: &[f64; 5] ) -> Verification
+{
+    let (xcrref, xceref): ([f64; 5], [f64; 5]) = match reference_norms(class_npb)
+    {
+        Some(norms) => norms,
+        None => return Verification::NotPerformed,
+    };
+
+    // a NaN difference fails too
+    let within = |(value, reference): (&f64, &f64)| -> bool
+    {
+        return relative_difference(value, reference) <= EPSILON;
+    };
+    return Verification::from(xcr.iter().zip(xcrref.iter()).all(within) &&
+                              xce.iter().zip(xceref.iter()).all(within));
+}
+
+
+
+// how to run SP
+#[derive(Clone, Debug)]
+pub struct Config
+{
+    pub class:      char,
+    pub backend:    BackendKind,
+    pub threads:    Option<usize>, // the backend's default if None
+    pub binding:    Binding,       // of the backend's threads
+    pub use_timers: bool,
+    pub progress:   bool,          // prints the time steps, as the reference NPB does
+    pub perf:       bool,          // counts the hardware events of the timed run
+}
+
+impl Config
+{
+    // a run of class 'class_npb', on 'backend' with its default threads
+    pub fn new( class_npb: &char, backend: &BackendKind ) -> Config
+    {
+        return Config
+        {
+            class: *class_npb, backend: *backend, threads: None, binding: Binding::None,
+            use_timers: false, progress: false, perf: false,
+        };
+    }
+}
+
+// the class table of SP: the grid and the time steps of a class, and the threads of a run
+#[derive(Clone, Copy, Debug)]
+pub struct SpClass
+{
+    pub class:       char,
+    pub grid_points: [usize; 3],
+    pub niter:       usize,
+    pub dt:          f64,
+    pub threads:     usize,
+}
+
+impl SpClass
+{
+    // the entry of the run 'config' describes
+    pub fn of( config: &Config ) -> Result<SpClass, String>
+    {
+        return match class_size(&config.class)
+        {
+            Some((problem_size, niter, dt)) => Ok(SpClass
+                                               {
+                                                   class: config.class,
+                                                   grid_points: [problem_size; 3], niter, dt,
+                                                   threads: config.backend.threads(&config.threads),
+                                               }),
+            None => Err(format!("SP has no class {}", config.class)),
+        };
+    }
+}
+
+impl ClassTable for SpClass
+{
+    fn kernel( &self ) -> &'static str
+    {
+        return "SP";
+    }
+
+    fn class( &self ) -> char
+    {
+        return self.class;
+    }
+
+    fn size( &self ) -> String
+    {
+        return format!("{}x{}x{}", self.grid_points[0], self.grid_points[1], self.grid_points[2]);
+    }
+
+    fn iterations( &self ) -> usize
+    {
+        return self.niter;
+    }
+
+    /* the solution, right hand side and forcing terms, and the z lines solved
+     * before being copied back (5 values per point), the 7 auxiliary arrays,
+     * and the line buffers of every thread
+     */
+    fn working_set( &self ) -> u64
+    {
+        let points: usize = self.grid_points.iter().product();
+        let line: usize = *self.grid_points.iter().max().unwrap();
+        return (((points * ((4 * 5) + 7)) + (self.threads * line * ((4 * 5) + 2))) *
+                std::mem::size_of::<f64>()) as u64;
+    }
+
+    fn seeds( &self ) -> Vec<(&'static str, f64)>
+    {
+        return Vec::new();
+    }
+}
+
+// the results of a run of SP
+#[derive(Clone, Debug)]
+pub struct SpResult
+{
+    pub class:        char,
+    pub grid_points:  [usize; 3],
+    pub niter:        usize,
+    pub backend:      &'static str,
+    pub threads:      usize,
+    pub xcr:          [f64; 5],      // RMS-norms of the residual
+    pub xce:          [f64; 5],      // RMS-norms of the solution error
+    pub time:         f64,
+    pub Mops:         f64,
+    pub timers:       [f64; T_LAST], // seconds, if timed (but T_TOTAL)
+    pub verification: Verification,
+    pub usage:        ResourceUsage,
+}
+
+impl SpResult
+{
+    // the comparison with the reference norms, as sp.f prints it (none without them)
+    pub fn comparison( &self ) -> String
+    {
+        let (xcrref, xceref): ([f64; 5], [f64; 5]) = match reference_norms(&self.class)
+        {
+            Some(norms) => norms,
+            None => return String::new(),
+        };
+
+        let mut out: String = String::new();
+        out += &format!(" Verification being performed for class {}\n", self.class);
+        out += &format!(" accuracy setting for epsilon = {:>20.13E}\n", EPSILON);
+        for (title, values, references) in [("residual", &self.xcr, &xcrref),
+                                            ("solution error", &self.xce, &xceref)]
+        {
+            out += &format!(" Comparison of RMS-norms of {}\n", title);
+            for m in 0usize..5
+            {
+                out += &format!("          {:>2} {:>20.13E} {:>20.13E} {:>20.13E}\n", m + 1,
+                                values[m], references[m],
+                                relative_difference(&values[m], &references[m]));
+            }
+        }
+        return out;
+    }
+}
+
+// runs SP through 'backend' as 'config' says
+fn run_on<B: ParallelBackend>( backend: &B, config: &Config, table: &SpClass ) -> SpResult
+{
+    let grid_points: [usize; 3] = table.grid_points;
+    let (mut xce, mut xcr): ([f64; 5], [f64; 5]) = ([0.0; 5], [0.0; 5]);
+
+    let mut sp: SP = SP::new(&grid_points, &table.dt, &config.use_timers);
+    sp.exact_rhs();
+    sp.initialize();
+
+    // do one time step to touch all code, and reinitialize
+    sp.adi(backend);
+    sp.initialize();
+    sp.timers = [0; T_LAST];
+
+    // starting main timer
+    let sampler: UsageSampler = UsageSampler::start(&config.perf);
+    let start_moment: Instant = Instant::now();
+
+    for step in 1usize..=table.niter
+    {
+        if config.progress && (((step % 20) == 0) || (step == 1))
+        {
+            println!(" Time step {:>4}", step);
+        }
+        sp.adi(backend);
+    }
+
+    sp.timers[T_TOTAL] = start_moment.elapsed().as_micros();
+    let usage: ResourceUsage = sampler.stop();
+    let time: f64 = (sp.timers[T_TOTAL] as f64) / 1e6;
+
+    // compute the error norm and the residual norm
+    sp.error_norm(&mut xce);
+    sp.compute_rhs(backend);
+    sp.rhs_norm(&mut xcr);
+    for xcr_m in xcr.iter_mut() { *xcr_m /= sp.dt; }
+
+    let mut Mops: f64 = 0.0;
+    if time != 0.0
+    {
+        let n3: f64 = (grid_points[0] * grid_points[1] * grid_points[2]) as f64;
+        let t: f64 = ((grid_points[0] + grid_points[1] + grid_points[2]) as f64) / 3.0;
+        Mops = ((881.174 * n3) - (4683.91 * (t * t)) + (11484.5 * t) - 19272.4) *
+               (table.niter as f64) / (time * 1000000.0);
+    }
+
+    return SpResult
+    {
+        class:        table.class,
+        grid_points,
+        niter:        table.niter,
+        backend:      backend.name(),
+        threads:      backend.num_threads(),
+        verification: verify(&table.class, &xcr, &xce),
+        xcr,
+        xce,
+        time,
+        Mops,
+        timers:       sp.timers.map(|t| (t as f64) / 1e6),
+        usage,
+    };
+}
+
+/* runs SP as 'config' says, printing nothing but the time steps if asked to;
+ * an error if its class is unknown, or its backend unavailable
+ */
+pub fn run( config: &Config ) -> Result<SpResult, String>
+{
+    let table: SpClass = SpClass::of(config)?;
+
+    return match config.backend
+    {
+        BackendKind::Serial => Ok(run_on(&Serial, config, &table)),
+        BackendKind::ThreadsChannels =>
+            Ok(run_on(&ThreadsChannels::with_binding(&table.threads, &config.binding), config,
+                      &table)),
+        #[cfg(feature = "rayon")]
+        BackendKind::Rayon => Ok(run_on(&Rayon::with_binding(&config.threads, &config.binding),
+                                        config, &table)),
+        #[cfg(not(feature = "rayon"))]
+        BackendKind::Rayon => Err(String::from("the Rayon backend needs the \"rayon\" feature")),
+    };
+}
@@ -0,0 +1,132 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    Summary statistics of repeated runs (the time and Mop/s samples of     //
+//    "--repeat N"), and their report.                                       //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+use crate::host::HostInfo;
+use crate::usage::ResourceUsage;
+use crate::verification::Verification;
+
+
+
+// two-sided 95% quantiles of Student's t distribution, for 1 to 30 degrees
+// of freedom (past which the normal one, 1.96, is close enough)
+const T_975: [f64; 30] = [12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262,
+                          2.228, 2.201, 2.179, 2.160, 2.145, 2.131, 2.120, 2.110, 2.101,
+                          2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056, 2.052,
+                          2.048, 2.045, 2.042];
+
+
+
+#[derive(Clone, Copy)]
+pub struct Summary
+{
+    pub min:    f64,
+    pub median: f64,
+    pub mean:   f64,
+    pub stddev: f64, // sample standard deviation (0 for a single sample)
+    pub ci95:   f64, // half-width of the 95% confidence interval of the mean
+}
+
+pub fn summarize( samples: &[f64] ) -> Summary
+{
+    let n: usize = samples.len();
+    let mut sorted: Vec<f64> = samples.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let median: f64 =
+            if n % 2 == 1 { sorted[n / 2] }
+            else { (sorted[(n / 2) - 1] + sorted[n / 2]) / 2.0 };
+    let mean: f64 = sorted.iter().sum::<f64>() / (n as f64);
+    let (stddev, ci95): (f64, f64) =
+            if n < 2 { (0.0, 0.0) }
+            else
+            {
+                let variance: f64 =
+                        sorted.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() /
+                        ((n - 1) as f64);
+                let t: f64 = if n - 1 <= T_975.len() { T_975[n - 2] } else { 1.96 };
+                (variance.sqrt(), t * variance.sqrt() / (n as f64).sqrt())
+            };
+
+    return Summary { min: sorted[0], median, mean, stddev, ci95 };
+}
+
+pub fn print_summary( seconds: &[f64], mops: &[f64], warmup: &usize )
+{
+	println!("\n Repeated runs: {} timed, {} warm-up\n", seconds.len(), warmup);
+	println!(" {:<15} {:>12} {:>12} {:>12} {:>12} {:>25}",
+	        "", "Min", "Median", "Mean", "Std. dev.", "95% CI of the mean");
+	for (label, samples) in [("Time in seconds", seconds), ("Mop/s total", mops)]
+	{
+	    let summary: Summary = summarize(samples);
+	    println!(" {:<15} {:>12.4} {:>12.4} {:>12.4} {:>12.4} {:>25}",
+	            label, summary.min, summary.median, summary.mean, summary.stddev,
+	            format!("[{:.4}, {:.4}]", summary.mean - summary.ci95,
+	                                      summary.mean + summary.ci95));
+	}
+}
+
+// 'x' as a JSON number, or null if it is infinite or NaN (which JSON cannot hold)
+fn json_number( x: &f64 ) -> String
+{
+    return if x.is_finite() { x.to_string() } else { String::from("null") };
+}
+
+// 'samples' and their summary, as a JSON object
+fn json_object( samples: &[f64] ) -> String
+{
+    let summary: Summary = summarize(samples);
+    let list: Vec<String> = samples.iter().map(json_number).collect();
+    return format!("{{ \"min\": {}, \"median\": {}, \"mean\": {}, \"stddev\": {}, \
+                    \"ci95\": [{}, {}], \"samples\": [{}] }}",
+                   json_number(&summary.min), json_number(&summary.median),
+                   json_number(&summary.mean), json_number(&summary.stddev),
+                   json_number(&(summary.mean - summary.ci95)),
+                   json_number(&(summary.mean + summary.ci95)), list.join(", "));
+}
+
+// the results of the (repeated) runs of a benchmark, as a JSON document (one
+// argument per field of it)
+#[allow(clippy::too_many_arguments)]
+pub fn to_json( name: &str, class_npb: &char, backend: &str, threads: &usize, warmup: &usize,
+                verification: &Verification, seconds: &[f64], mops: &[f64],
+                usage: &ResourceUsage ) -> String
+{
+    return format!("{{\n  \"benchmark\": \"{}\",\n  \"class\": \"{}\",\n  \"backend\": \"{}\",\n  \
+                    \"threads\": {},\n  \"warmup\": {},\n  \"verification\": \"{}\",\n  \
+                    \"time\": {},\n  \"mops\": {},\n  \"usage\": {},\n  \"host\": {}\n}}\n",
+                   name, class_npb, backend, threads, warmup, verification.label(),
+                   json_object(seconds), json_object(mops), usage.to_json(),
+                   HostInfo::collect().to_json());
+}
@@ -0,0 +1,106 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    Checks of the comparison of a run with a baseline: which deltas are    //
+//    regressions, and which pairs of runs cannot be compared at all.        //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+#![allow(clippy::needless_return)]
+
+use std::env;
+use std::fs;
+
+use rusty_npb::baseline;
+use rusty_npb::stats;
+use rusty_npb::usage::ResourceUsage;
+use rusty_npb::verification::Verification;
+
+
+
+// the "--json" document of a serial run of EP of class S taking 'seconds'
+fn document( threads: &usize, seconds: &[f64] ) -> String
+{
+    let mops: Vec<f64> = seconds.iter().map(|s| 100.0 / s).collect();
+    return stats::to_json("EP", &'S', "serial", threads, &0, &Verification::Successful, seconds,
+                          &mops, &ResourceUsage::default());
+}
+
+// compares 'current' with the baseline 'saved' (written to a file named after 'test')
+fn compare( test: &str, saved: &str, current: &str, threshold: &f64 ) -> Result<bool, String>
+{
+    let path: String = env::temp_dir().join(format!("rusty-npb-baseline-{}-{}.json", test,
+                                                    std::process::id()))
+                                      .to_string_lossy().into_owned();
+    fs::write(&path, saved).unwrap();
+    let outcome: Result<bool, String> = baseline::compare(current, &path, threshold);
+    fs::remove_file(&path).unwrap();
+    return outcome;
+}
+
+#[test]
+fn only_slowdowns_past_the_threshold_are_regressions()
+{
+    let saved: String = document(&1, &[1.0, 1.0]);
+    assert_eq!(compare("same", &saved, &saved, &5.0), Ok(false));
+    assert_eq!(compare("faster", &saved, &document(&1, &[0.5, 0.5]), &5.0), Ok(false));
+    assert_eq!(compare("within", &saved, &document(&1, &[1.04, 1.04]), &5.0), Ok(false));
+    assert_eq!(compare("slower", &saved, &document(&1, &[1.1, 1.1]), &5.0), Ok(true));
+    assert_eq!(compare("threshold", &saved, &document(&1, &[1.1, 1.1]), &20.0), Ok(false));
+}
+
+#[test]
+fn different_runs_are_not_compared()
+{
+    let saved: String = document(&1, &[1.0]);
+    assert!(compare("threads", &saved, &document(&2, &[1.0]), &5.0).is_err());
+    assert!(compare("invalid", "{ \"benchmark\": ", &saved, &5.0).is_err());
+    assert!(baseline::compare(&saved, "/nonexistent/baseline.json", &5.0).is_err());
+}
+
+#[test]
+fn non_finite_metrics_are_not_compared()
+{
+    let finite: String = document(&1, &[1.0]);
+    let infinite: String = document(&1, &[f64::INFINITY]);
+    assert!(compare("current", &finite, &infinite, &5.0).is_err());
+    assert!(compare("saved", &infinite, &finite, &5.0).is_err());
+    assert!(compare("zero", &document(&1, &[0.0]), &finite, &5.0).is_err());
+}
+
+#[test]
+fn thresholds_are_read_from_the_arguments()
+{
+    let args = |list: &[&str]| list.iter().map(|a| a.to_string()).collect::<Vec<String>>();
+    assert_eq!(baseline::threshold(&args(&["EP"])), Ok(5.0));
+    assert_eq!(baseline::threshold(&args(&["EP", "--threshold", "12.5%"])), Ok(12.5));
+    assert!(baseline::threshold(&args(&["EP", "--threshold", "-1"])).is_err());
+    assert!(baseline::threshold(&args(&["EP", "--threshold", "x"])).is_err());
+}
@@ -0,0 +1,106 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    Helpers of the tests of the binaries of Serial, Rayon and TC, which    //
+//    include this file through #[path]: they run a binary, read the fields  //
+//    of its results block and check that it verified, and that EP printed   //
+//    the reference sums of the official NPB for the class it was built for  //
+//    (CLASS).                                                               //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+#![allow(non_snake_case)]
+#![allow(clippy::needless_return)]
+// each test file uses only a part of these
+#![allow(dead_code)]
+
+
+
+use std::process::{Command, Output};
+
+use rusty_npb::ep;
+use rusty_npb::verification::EXIT_SUCCESSFUL;
+
+
+
+// the class the binaries were built for
+pub const CLASS: &str = env!("CLASS");
+
+// how close to the reference sums the printed ones must be (as in ep::verify)
+pub const EPSILON: f64 = 1.0e-8;
+
+// what the binary 'kernel' prints when run with 'args', and its exit code
+pub fn run( kernel: &str, args: &[&str] ) -> (String, i32)
+{
+    let output: Output = Command::new(kernel).args(args).output().unwrap();
+    return (String::from_utf8_lossy(&output.stdout).into_owned(), output.status.code().unwrap());
+}
+
+// the value of the report line "<label> = <value>" of 'output'
+pub fn field<'a>( output: &'a str, label: &str ) -> &'a str
+{
+    return output.lines().filter(|line| line.trim_start().starts_with(label))
+                 .find_map(|line| line.split_once('=')).map(|(_, value)| value.trim())
+                 .unwrap_or_else(|| panic!("no \"{}\" in:\n{}", label, output));
+}
+
+// runs the binary 'kernel' with 'args', checking that it verified (printed so
+// and exited successfully); returns what it printed
+pub fn check_verifies( kernel: &str, args: &[&str] ) -> String
+{
+    let (output, code): (String, i32) = run(kernel, args);
+    assert_eq!(code, EXIT_SUCCESSFUL, "{}", output);
+    assert_eq!(field(&output, "Verification"), "SUCCESSFUL", "{}", output);
+    return output;
+}
+
+// the sums (sx, sy) printed in 'output', sy on the line after sx
+pub fn printed_sums( output: &str ) -> (f64, f64)
+{
+    let mut lines = output.lines().skip_while(|line| !line.trim_start().starts_with("Sums"));
+    let sx: f64 = lines.next().and_then(|line| line.split_once('=')).unwrap().1
+                       .trim().parse().unwrap();
+    let sy: f64 = lines.next().unwrap().trim().parse().unwrap();
+    return (sx, sy);
+}
+
+// runs EP (the binary 'kernel') with 'args', checking it verified and printed
+// the reference sums of CLASS
+pub fn check_ep( kernel: &str, args: &[&str] ) -> (f64, f64)
+{
+    let output: String = check_verifies(kernel, args);
+
+    let M: usize = ep::class_size(&CLASS.chars().next().unwrap()).unwrap();
+    let (sx_reference, sy_reference): (f64, f64) = ep::reference_sums(&M).unwrap();
+    let (sx, sy): (f64, f64) = printed_sums(&output);
+    assert!(((sx - sx_reference) / sx_reference).abs() <= EPSILON, "sx = {}", sx);
+    assert!(((sy - sy_reference) / sy_reference).abs() <= EPSILON, "sy = {}", sy);
+    return (sx, sy);
+}
@@ -0,0 +1,148 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    Checks of EP against the reference sums of the official NPB, run on    //
+//    every backend this build has (Rayon only with the "rayon" feature),    //
+//    which must also agree with each other. Class W takes a few seconds     //
+//    per backend, so it is ignored unless asked for:                        //
+//    cargo test -- --ignored                                                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+#![allow(non_snake_case)]
+#![allow(clippy::needless_return)]
+
+use rusty_npb::backend::BackendKind;
+use rusty_npb::ep::{self, Config, EpClass, EpResult};
+use rusty_npb::verification::Verification;
+
+
+
+// how close to the reference sums a run must be (as in ep::verify)
+const EPSILON: f64 = 1.0e-8;
+
+// how close the runs of different backends must be (they only differ in the
+// order their partial sums are added up)
+const AGREEMENT: f64 = 1.0e-12;
+
+fn relative_error( value: &f64, reference: &f64 ) -> f64
+{
+    return ((value - reference) / reference).abs();
+}
+
+// runs EP of class 'class_npb' on every available backend, with 'threads'
+// for the parallel ones
+fn run_all( class_npb: &char, threads: &usize ) -> Vec<EpResult>
+{
+    return BackendKind::available().iter().map(|backend|
+    {
+        let mut config: Config = Config::new(class_npb, backend);
+        config.threads = Some(*threads);
+        return ep::run(&config).unwrap();
+    }).collect();
+}
+
+fn check_class( class_npb: &char )
+{
+    let M: usize = ep::class_size(class_npb).unwrap();
+    let (sx_reference, sy_reference): (f64, f64) = ep::reference_sums(&M).unwrap();
+    let results: Vec<EpResult> = run_all(class_npb, &3);
+
+    for result in results.iter()
+    {
+        assert_eq!(result.verification, Verification::Successful, "{}", result.backend);
+        assert!(relative_error(&result.sx, &sx_reference) <= EPSILON,
+                "{}: sx = {:.15e}, not {:.15e}", result.backend, result.sx, sx_reference);
+        assert!(relative_error(&result.sy, &sy_reference) <= EPSILON,
+                "{}: sy = {:.15e}, not {:.15e}", result.backend, result.sy, sy_reference);
+    }
+
+    let first: &EpResult = &results[0];
+    for result in results[1..].iter()
+    {
+        assert!(relative_error(&result.sx, &first.sx) <= AGREEMENT,
+                "sx: {} {:.15e}, {} {:.15e}", first.backend, first.sx, result.backend, result.sx);
+        assert!(relative_error(&result.sy, &first.sy) <= AGREEMENT,
+                "sy: {} {:.15e}, {} {:.15e}", first.backend, first.sy, result.backend, result.sy);
+        assert_eq!(result.q, first.q, "{} and {}", first.backend, result.backend);
+        assert_eq!(result.gaussian_pairs, first.gaussian_pairs);
+    }
+}
+
+#[test]
+fn class_s_matches_the_reference_on_every_backend()
+{
+    check_class(&'S');
+}
+
+#[test]
+#[ignore]
+fn class_w_matches_the_reference_on_every_backend()
+{
+    check_class(&'W');
+}
+
+#[test]
+fn sums_do_not_depend_on_the_number_of_threads()
+{
+    let one: Vec<EpResult> = run_all(&'S', &1);
+    let many: Vec<EpResult> = run_all(&'S', &4);
+
+    for (a, b) in one.iter().zip(many.iter())
+    {
+        assert!(relative_error(&a.sx, &b.sx) <= AGREEMENT, "{}", a.backend);
+        assert!(relative_error(&a.sy, &b.sy) <= AGREEMENT, "{}", a.backend);
+        assert_eq!(a.q, b.q, "{}", a.backend);
+    }
+}
+
+#[test]
+fn unknown_sizes_are_not_verified()
+{
+    assert_eq!(ep::reference_sums(&26), None);
+    assert_eq!(ep::verify(&26, &0.0, &0.0), Verification::NotPerformed);
+    assert_eq!(ep::verify(&24, &-3.2478e+3, &-6.9584e+3), Verification::Unsuccessful);
+}
+
+#[test]
+fn sizes_out_of_range_are_errors()
+{
+    let mut config: Config = Config::new(&'S', &BackendKind::Serial);
+    for M in [0usize, ep::MK - 1, ep::MK + (usize::BITS as usize)]
+    {
+        config.M = Some(M);
+        assert!(EpClass::of(&config).is_err(), "M = {}", M);
+        assert!(ep::run(&config).is_err(), "M = {}", M);
+    }
+
+    config.M = Some(ep::MK);
+    assert_eq!(EpClass::of(&config).unwrap().M, ep::MK);
+    assert_eq!(ep::run(&config).unwrap().verification, Verification::NotPerformed);
+}
@@ -0,0 +1,88 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    Checks of the JSON reader, on hand-written documents and on the ones   //
+//    "--json" writes, which must stay valid JSON even when a run's time or  //
+//    Mop/s is not a finite number.                                          //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+#![allow(clippy::needless_return)]
+
+use rusty_npb::json::{self, Value};
+use rusty_npb::stats;
+use rusty_npb::usage::ResourceUsage;
+use rusty_npb::verification::Verification;
+
+
+
+#[test]
+fn documents_are_read_back()
+{
+    let document: Value =
+            json::parse(" { \"a\": [1, -2.5e3, true, null], \"b\": { \"c\": \"x\\\"\\u00e7\\n\" }, \
+                         \"d\": false } ").unwrap();
+
+    assert_eq!(document.get("a"), Some(&Value::Array(vec![Value::Number(1.0),
+                                                          Value::Number(-2500.0),
+                                                          Value::Bool(true), Value::Null])));
+    assert_eq!(document.get("b").and_then(|b| b.get("c")).and_then(|c| c.as_str()),
+               Some("x\"ç\n"));
+    assert_eq!(document.get("d").and_then(|d| d.as_bool()), Some(false));
+    assert_eq!(document.get("e"), None);
+    assert_eq!(json::parse("[]"), Ok(Value::Array(Vec::new())));
+    assert_eq!(json::parse("{}"), Ok(Value::Object(Vec::new())));
+}
+
+#[test]
+fn malformed_documents_are_errors()
+{
+    for text in ["", "{", "[1, 2", "{\"a\" 1}", "{\"a\": 1,}", "[1] 2", "\"abc", "\"\\q\"",
+                 "nul", "-", "1e999", "NaN", "inf"]
+    {
+        assert!(json::parse(text).is_err(), "{}", text);
+    }
+}
+
+#[test]
+fn results_are_valid_json_with_any_samples()
+{
+    for samples in [vec![1.5, 2.5], vec![f64::INFINITY], vec![2.0, f64::NAN]]
+    {
+        let text: String = stats::to_json("EP", &'S', "serial", &1, &0, &Verification::Successful,
+                                          &samples, &samples, &ResourceUsage::default());
+        let document: Value = json::parse(&text).unwrap_or_else(|e| panic!("{}:\n{}", e, text));
+
+        let time: &Value = document.get("time").unwrap();
+        let finite: bool = samples.iter().all(|x| x.is_finite());
+        assert_eq!(time.get("mean").and_then(|m| m.as_f64()), if finite { Some(2.0) } else { None });
+        assert_eq!(document.get("benchmark").and_then(|b| b.as_str()), Some("EP"));
+    }
+}
@@ -0,0 +1,131 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    Checks of SP, LU and DT run through the library at class S, on every   //
+//    backend, against the reference values of the official NPB and the      //
+//    serial run, and of the errors of the runs it cannot do. LU of class A  //
+//    takes about a minute in release, so it is ignored unless asked for:    //
+//    cargo test --release -- --ignored                                      //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+#![allow(clippy::needless_return)]
+
+use rusty_npb::backend::BackendKind;
+use rusty_npb::dt;
+use rusty_npb::lu::{self, LuResult};
+use rusty_npb::sp::{self, SpResult};
+use rusty_npb::verification::Verification;
+
+
+
+// the runs of 'config' on every available backend, the serial one first
+fn on_every_backend<C: Clone, R>( config: &C, set: fn( &mut C, BackendKind ),
+                                  run: fn( &C ) -> Result<R, String> ) -> Vec<(BackendKind, R)>
+{
+    let mut results: Vec<(BackendKind, R)> = Vec::new();
+    for backend in BackendKind::available()
+    {
+        let mut config: C = config.clone();
+        set(&mut config, backend);
+        results.push((backend, run(&config).unwrap()));
+    }
+    return results;
+}
+
+
+
+#[test]
+fn sp_class_s_verifies_on_every_backend()
+{
+    let mut config: sp::Config = sp::Config::new(&'S', &BackendKind::Serial);
+    config.threads = Some(3);
+    let results: Vec<(BackendKind, SpResult)> =
+        on_every_backend(&config, |c, b| c.backend = b, sp::run);
+    for (backend, result) in &results
+    {
+        assert_eq!(result.verification, Verification::Successful,
+                   "{}: {}", backend.name(), result.comparison());
+        assert_eq!((result.grid_points, result.niter), ([12; 3], 100));
+        assert_eq!((result.xcr, result.xce), (results[0].1.xcr, results[0].1.xce),
+                   "{}", backend.name());
+    }
+}
+
+#[test]
+fn lu_class_s_verifies_on_every_backend()
+{
+    let mut config: lu::Config = lu::Config::new(&'S', &BackendKind::Serial);
+    config.threads = Some(3);
+    let results: Vec<(BackendKind, LuResult)> =
+        on_every_backend(&config, |c, b| c.backend = b, lu::run);
+    for (backend, result) in &results
+    {
+        assert_eq!(result.verification, Verification::Successful,
+                   "{}: {}", backend.name(), result.comparison());
+        assert_eq!((result.n, result.itmax), ([12; 3], 50));
+        let serial: &LuResult = &results[0].1;
+        assert_eq!((result.xcr, result.xce, result.xci), (serial.xcr, serial.xce, serial.xci),
+                   "{}", backend.name());
+    }
+}
+
+#[test]
+#[ignore]
+fn lu_class_a_verifies()
+{
+    let result: LuResult = lu::run(&lu::Config::new(&'A', &BackendKind::Serial)).unwrap();
+    assert_eq!(result.verification, Verification::Successful, "{}", result.comparison());
+}
+
+#[test]
+fn dt_class_s_verifies_on_every_backend()
+{
+    for graph in ["BH", "WH", "SH"]
+    {
+        let mut config: dt::Config = dt::Config::new(&'S', graph, &BackendKind::Serial);
+        config.threads = Some(3);
+        for (backend, result) in on_every_backend(&config, |c, b| c.backend = b, dt::run)
+        {
+            assert_eq!(result.verification, Verification::Successful,
+                       "{} {}: {}", backend.name(), graph, result.comparison());
+            assert_eq!(result.chksum, dt::reference_value(&'S', graph).unwrap());
+        }
+    }
+}
+
+#[test]
+fn unknown_classes_and_graphs_are_errors()
+{
+    assert!(sp::run(&sp::Config::new(&'X', &BackendKind::Serial)).is_err());
+    assert!(lu::run(&lu::Config::new(&'X', &BackendKind::Serial)).is_err());
+    assert!(dt::run(&dt::Config::new(&'S', "XH", &BackendKind::Serial)).is_err());
+    assert!(dt::run(&dt::Config::new(&'E', "BH", &BackendKind::Serial)).is_err());
+}
@@ -0,0 +1,272 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    Snapshot tests of the results blocks (print_results and EP's), which   //
+//    log parsers depend on: reports of fixed runs, builds and hosts are     //
+//    rendered and compared with the saved ones in tests/snapshots. A        //
+//    deliberate change of the layout is accepted by running them with       //
+//    UPDATE_SNAPSHOTS=1, which saves the new renderings instead.            //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+#![allow(clippy::needless_return)]
+
+
+
+use std::env;
+use std::fs;
+
+use rusty_npb::ep::{EpResult, WorkerTimes};
+use rusty_npb::host::HostInfo;
+use rusty_npb::perf::PerfCounts;
+use rusty_npb::report::{self, BenchmarkReport, BuildInfo};
+use rusty_npb::usage::ResourceUsage;
+use rusty_npb::verification::Verification;
+
+
+
+// compares 'rendered' with the snapshot 'name' (or saves it, if updating them)
+fn check_snapshot( name: &str, rendered: &str )
+{
+    let path: String = format!("{}/tests/snapshots/{}.txt", env!("CARGO_MANIFEST_DIR"), name);
+    if env::var_os("UPDATE_SNAPSHOTS").is_some()
+    {
+        fs::write(&path, rendered).unwrap();
+        return;
+    }
+
+    let expected: String = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("{}: {} (UPDATE_SNAPSHOTS=1 saves it)", path, e));
+    if let Some((i, (got, want))) = rendered.lines().zip(expected.lines()).enumerate()
+                                            .find(|(_, (got, want))| got != want)
+    {
+        panic!("{} differs at line {}:\n   got: {:?}\n  want: {:?}\n(UPDATE_SNAPSHOTS=1 accepts it)",
+               path, i + 1, got, want);
+    }
+    assert_eq!(rendered, expected, "{} differs in its number of lines", path);
+}
+
+fn build( rayon_version: Option<&'static str> ) -> BuildInfo
+{
+    return BuildInfo
+    {
+        package: "Rusty-NPB",
+        authors: "Pedro Vernetti G. <pedrovernetti@gmail.com>",
+        version: "0.1.0",
+        compile_date: "2023-01-01",
+        rustc_version: "rustc 1.70.0 (90c541806 2023-05-31)",
+        rustc: "rustc",
+        rustflags: "",
+        profile: "release (opt-level=3, debug=false)",
+        target: "x86_64-unknown-linux-gnu",
+        target_cpu: "x86-64",
+        target_features: "fxsr,sse,sse2",
+        dependencies: vec!["Rusty-NPB-common 0.1.0", "pkg_compile_time 0.1.3"],
+        rayon_version,
+    };
+}
+
+fn host() -> HostInfo
+{
+    return HostInfo
+    {
+        cpu_model: String::from("Example CPU @ 3.00GHz"),
+        sockets: 1,
+        cores: 4,
+        threads: 8,
+        caches: vec![String::from("L1d 32K"), String::from("L2 256K"), String::from("L3 8192K")],
+        numa_nodes: 1,
+        memory_kb: 16777216,
+        kernel: String::from("6.1.0"),
+        governor: String::from("performance"),
+        allowed_cpus: String::from("0-7"),
+    };
+}
+
+fn usage( counters: Option<PerfCounts> ) -> ResourceUsage
+{
+    return ResourceUsage
+    {
+        peak_rss_kb: 4752, minor_faults: 259, major_faults: 0,
+        voluntary_switches: 3, involuntary_switches: 40, counters,
+    };
+}
+
+// the report of a class S run of EP
+fn report( threads: &str, requested_threads: Option<usize>, counters: Option<PerfCounts>,
+           rayon_version: Option<&'static str> ) -> BenchmarkReport
+{
+    let available_threads: Option<usize> =
+            if threads == "1" { None } else { Some(requested_threads.unwrap_or(8)) };
+
+    return BenchmarkReport
+    {
+        name: String::from("EP"),
+        class: 'S',
+        size: BenchmarkReport::size("EP", &25, &0, &0),
+        total_threads: threads.to_string(),
+        requested_threads,
+        available_threads,
+        iterations: 0,
+        time: 1.580337476,
+        mops: 21.23244718902053,
+        optype: String::from("Random numbers generated"),
+        verification: Verification::Successful,
+        usage: usage(counters),
+        build: build(rayon_version),
+        host: host(),
+    };
+}
+
+#[test]
+fn serial_report()
+{
+    check_snapshot("report_serial", &report("1", None, None, None).render());
+}
+
+#[test]
+fn rayon_report_with_fewer_threads_than_requested()
+{
+    check_snapshot("report_rayon", &report("2", Some(4), None, Some("1.8.0")).render());
+}
+
+#[test]
+fn report_with_hardware_counters()
+{
+    let counters: PerfCounts = PerfCounts
+    {
+        cycles: Some(4_740_000_000), instructions: Some(9_950_000_000), cache_misses: Some(1_234_567),
+        branch_misses: Some(23_456_789), fp_ops: None,
+    };
+    check_snapshot("report_counters", &report("4", Some(4), Some(counters), None).render());
+}
+
+#[test]
+fn report_with_unavailable_hardware_counters()
+{
+    let rendered: String = report("1", None, Some(PerfCounts::default()), None).render();
+    assert!(rendered.contains("\n HW counters     =                    unavailable\n"));
+}
+
+#[test]
+fn sizes()
+{
+    assert_eq!(BenchmarkReport::size("EP", &25, &0, &0), "33554432");
+    assert_eq!(BenchmarkReport::size("IS", &65536, &0, &0), "65536");
+    assert_eq!(BenchmarkReport::size("IS", &1024, &64, &0), "65536");
+    assert_eq!(BenchmarkReport::size("DC", &1000, &0, &0), "1000");
+    assert_eq!(BenchmarkReport::size("SP", &12, &12, &12), "  12x  12x  12");
+}
+
+fn ep_result() -> EpResult
+{
+    return EpResult
+    {
+        class: 'S',
+        M: 24,
+        backend: "tc",
+        threads: 2,
+        binding: String::from("compact (0, 1)"),
+        sx: -3247.8346520347386,
+        sy: -6958.407078382299,
+        q: [6140517.0, 5865300.0, 1100361.0, 68546.0, 1648.0, 17.0, 0.0, 0.0, 0.0, 0.0],
+        gaussian_pairs: 13176389.0,
+        seconds: vec![1.580337476],
+        mops: vec![21.23244718902053],
+        time: 1.580337476,
+        Mops: 21.23244718902053,
+        time_gaussian: 0.0,
+        time_random: 0.0,
+        workers: vec![WorkerTimes::default(); 2],
+        trace: Vec::new(),
+        verification: Verification::Successful,
+        usage: usage(None),
+    };
+}
+
+#[test]
+fn ep_results()
+{
+    check_snapshot("ep_results", &ep_result().summary());
+}
+
+#[test]
+fn npb_serial_report()
+{
+    check_snapshot("npb_report_serial", &report("1", None, None, None).render_npb());
+}
+
+#[test]
+fn npb_openmp_report_with_fewer_threads_than_available()
+{
+    check_snapshot("npb_report_openmp", &report("2", Some(4), None, Some("1.8.0")).render_npb());
+}
+
+#[test]
+fn npb_grid_report()
+{
+    let mut sp: BenchmarkReport = report("1", None, None, None);
+    sp.name = String::from("SP");
+    sp.size = BenchmarkReport::size("SP", &12, &12, &12);
+    sp.iterations = 100;
+    sp.optype = String::from("floating point");
+    sp.verification = Verification::Unsuccessful;
+    check_snapshot("npb_report_grid", &sp.render_npb());
+}
+
+// DT and DC are written in C by the reference NPB, whose block differs slightly
+#[test]
+fn npb_c_report()
+{
+    let mut dt: BenchmarkReport = report("4", None, None, None);
+    dt.name = String::from("DT");
+    dt.size = BenchmarkReport::size("DT", &19, &0, &0);
+    dt.iterations = 26;
+    dt.optype = String::from("bytes transmitted");
+    dt.verification = Verification::NotPerformed;
+    dt.build.compile_date = "not a date";
+    check_snapshot("npb_report_c", &dt.render_npb());
+}
+
+#[test]
+fn npb_ep_results()
+{
+    check_snapshot("npb_ep_results", &ep_result().summary_npb());
+}
+
+#[test]
+fn fortran_edit_descriptors()
+{
+    assert_eq!(report::fortran_d(&-3247.8346520347386, &25, &15), "   -3.247834652034739D+03");
+    assert_eq!(report::fortran_d(&0.000125, &12, &3), "   1.250D-04");
+    assert_eq!(report::fortran_d(&0.0, &10, &2), "  0.00D+00");
+    assert_eq!(report::fortran_f0(&13176389.0, &15), "      13176389.");
+    assert_eq!(report::fortran_f0(&0.0, &4), "  0.");
+}
@@ -0,0 +1,93 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    Checks of the summary of repeated runs: the order statistics, and the  //
+//    confidence interval of the mean, whose Student's t quantile must be    //
+//    the one of n - 1 degrees of freedom up to the end of the table.        //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+#![allow(clippy::needless_return)]
+
+use rusty_npb::stats::{self, Summary};
+
+
+
+const TOLERANCE: f64 = 1.0e-12;
+
+// the samples 0, 1, ..., n - 1, shuffled (for any n that 7 does not divide)
+fn samples( n: &usize ) -> Vec<f64>
+{
+    return (0usize..*n).map(|i| ((i * 7) % n) as f64).collect();
+}
+
+// the half-width of the 95% CI of 'samples(n)', with the quantile 't'
+fn expected_ci95( n: &usize, t: &f64 ) -> f64
+{
+    let mean: f64 = ((n - 1) as f64) / 2.0;
+    let variance: f64 =
+            (0usize..*n).map(|i| (i as f64 - mean) * (i as f64 - mean)).sum::<f64>() /
+            ((n - 1) as f64);
+    return t * variance.sqrt() / (*n as f64).sqrt();
+}
+
+#[test]
+fn a_single_sample_has_no_spread()
+{
+    let summary: Summary = stats::summarize(&[2.5]);
+    assert_eq!((summary.min, summary.median, summary.mean), (2.5, 2.5, 2.5));
+    assert_eq!((summary.stddev, summary.ci95), (0.0, 0.0));
+}
+
+#[test]
+fn order_statistics_ignore_the_order_of_the_samples()
+{
+    let summary: Summary = stats::summarize(&[4.0, 1.0, 3.0, 2.0]);
+    assert_eq!(summary.min, 1.0);
+    assert_eq!(summary.median, 2.5);
+    assert_eq!(summary.mean, 2.5);
+
+    let summary: Summary = stats::summarize(&[5.0, 1.0, 3.0]);
+    assert_eq!(summary.median, 3.0);
+    assert_eq!(summary.stddev, 2.0);
+}
+
+#[test]
+fn the_quantile_follows_the_degrees_of_freedom()
+{
+    // 1, 29 and 30 degrees of freedom are in the table, 31 is past it
+    for (n, t) in [(2usize, 12.706), (30, 2.045), (31, 2.042), (32, 1.96)]
+    {
+        let summary: Summary = stats::summarize(&samples(&n));
+        let expected: f64 = expected_ci95(&n, &t);
+        assert!((summary.ci95 - expected).abs() <= TOLERANCE * expected,
+                "{} samples: {} instead of {}", n, summary.ci95, expected);
+    }
+}
@@ -0,0 +1,111 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    Spans of time of the threads of a run (its initialization, batches,    //
+//    random number generation, computation and reduction), written in the   //
+//    Chrome Trace Event format, which trace viewers (chrome://tracing,      //
+//    Perfetto) open as one timeline per thread.                             //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+use std::time::Instant;
+
+
+
+// the thread of the spans belonging to no worker
+pub const MAIN_THREAD: usize = 0;
+
+// a span of a thread, in microseconds since the trace started
+#[derive(Clone, Debug, PartialEq)]
+pub struct Span
+{
+    pub name:     String,
+    pub category: &'static str, // "run", "init", "batch", "rng", "compute" or "reduction"
+    pub thread:   usize,        // MAIN_THREAD, or 1 + the index of a worker
+    pub start:    f64,
+    pub duration: f64,
+}
+
+impl Span
+{
+    // the span between 'start' and 'end', in seconds since the trace started
+    pub fn new( name: &str, category: &'static str, thread: &usize, start: &f64, end: &f64 )
+            -> Span
+    {
+        return Span
+        {
+            name: name.to_string(), category, thread: *thread,
+            start: start * 1e6, duration: (end - start) * 1e6,
+        };
+    }
+}
+
+// the seconds between 'origin' and now
+pub fn since( origin: &Instant ) -> f64
+{
+    return origin.elapsed().as_secs_f64();
+}
+
+// 'text' as a JSON string
+fn quoted( text: &str ) -> String
+{
+    return format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+}
+
+/* the spans as a Chrome Trace Event format file: a "complete" event per span,
+ * and the names of the process and of its threads
+ */
+pub fn to_json( process: &str, spans: &[Span] ) -> String
+{
+    let mut threads: Vec<usize> = spans.iter().map(|span| span.thread).collect();
+    threads.sort_unstable();
+    threads.dedup();
+
+    let mut events: Vec<String> = Vec::with_capacity(spans.len() + threads.len() + 1);
+    events.push(format!("{{ \"name\": \"process_name\", \"ph\": \"M\", \"pid\": 1, \"tid\": 0, \
+                         \"args\": {{ \"name\": {} }} }}", quoted(process)));
+    for thread in threads
+    {
+        let name: String = if thread == MAIN_THREAD { String::from("main") }
+                           else { format!("worker {}", thread - 1) };
+        events.push(format!("{{ \"name\": \"thread_name\", \"ph\": \"M\", \"pid\": 1, \"tid\": {}, \
+                             \"args\": {{ \"name\": {} }} }}", thread, quoted(&name)));
+    }
+    for span in spans
+    {
+        events.push(format!("{{ \"name\": {}, \"cat\": {}, \"ph\": \"X\", \"pid\": 1, \"tid\": {}, \
+                             \"ts\": {:.3}, \"dur\": {:.3} }}",
+                            quoted(&span.name), quoted(span.category), span.thread, span.start,
+                            span.duration));
+    }
+
+    return format!("{{ \"displayTimeUnit\": \"ms\", \"traceEvents\": [\n  {}\n] }}\n",
+                   events.join(",\n  "));
+}
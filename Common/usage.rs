@@ -0,0 +1,163 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    What the timed region of a benchmark cost the process: peak resident   //
+//    memory, page faults and context switches, sampled through getrusage and//
+//    /proc/self/status before and after it.                                 //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+use std::fs;
+
+use crate::perf::{PerfCounters, PerfCounts};
+
+
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ResourceUsage
+{
+    pub peak_rss_kb:          u64, // during the region, if the peak could be reset
+    pub minor_faults:         u64,
+    pub major_faults:         u64,
+    pub voluntary_switches:   u64,
+    pub involuntary_switches: u64,
+    pub counters:             Option<PerfCounts>, // if the hardware events were counted
+}
+
+// the counters of getrusage for the whole process (all of its threads)
+#[cfg(target_os = "linux")]
+fn counters() -> ResourceUsage
+{
+    // SAFETY: 'usage' is a properly sized rusage, only read after the call
+    unsafe
+    {
+        let mut usage: libc::rusage = std::mem::zeroed();
+        if libc::getrusage(libc::RUSAGE_SELF, &mut usage) != 0 { return ResourceUsage::default(); }
+        return ResourceUsage
+        {
+            peak_rss_kb:          usage.ru_maxrss as u64, // in kilobytes on Linux
+            minor_faults:         usage.ru_minflt as u64,
+            major_faults:         usage.ru_majflt as u64,
+            voluntary_switches:   usage.ru_nvcsw as u64,
+            involuntary_switches: usage.ru_nivcsw as u64,
+            counters:             None,
+        };
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn counters() -> ResourceUsage
+{
+    return ResourceUsage::default();
+}
+
+// the peak resident set size so far (VmHWM), in kilobytes
+fn high_water_mark() -> Option<u64>
+{
+    let status: String = fs::read_to_string("/proc/self/status").ok()?;
+    return status.lines().find(|line| line.starts_with("VmHWM:"))
+                 .and_then(|line| line.split_whitespace().nth(1))
+                 .and_then(|kb| kb.parse::<u64>().ok());
+}
+
+// the resource usage of a region, from start() to stop()
+pub struct UsageSampler
+{
+    before:     ResourceUsage,
+    peak_reset: bool,
+    counters:   Option<PerfCounters>,
+}
+
+impl UsageSampler
+{
+    /* takes the starting sample, resetting the process' peak RSS first (so
+     * the one measured belongs to the region), where the kernel allows it, and
+     * starting the hardware event counters if 'count_events'
+     */
+    pub fn start( count_events: &bool ) -> UsageSampler
+    {
+        let peak_reset: bool = fs::write("/proc/self/clear_refs", "5").is_ok();
+        let events: Option<PerfCounters> = if *count_events { Some(PerfCounters::start()) } else { None };
+        return UsageSampler { before: counters(), peak_reset, counters: events };
+    }
+
+    /* the usage since start(); the peak RSS is the process' own one if it
+     * could not be reset
+     */
+    pub fn stop( &self ) -> ResourceUsage
+    {
+        let events: Option<PerfCounts> = self.counters.as_ref().map(|counters| counters.read());
+        let after: ResourceUsage = counters();
+        let peak_rss_kb: u64 =
+                if self.peak_reset { high_water_mark().unwrap_or(after.peak_rss_kb) }
+                else { after.peak_rss_kb };
+        return ResourceUsage
+        {
+            peak_rss_kb,
+            minor_faults:         after.minor_faults - self.before.minor_faults,
+            major_faults:         after.major_faults - self.before.major_faults,
+            voluntary_switches:   after.voluntary_switches - self.before.voluntary_switches,
+            involuntary_switches: after.involuntary_switches - self.before.involuntary_switches,
+            counters:             events,
+        };
+    }
+}
+
+impl ResourceUsage
+{
+    // the usage lines of the report (see report::BenchmarkReport)
+    pub fn render( &self ) -> String
+    {
+        let mut out: String = String::new();
+	    out += &format!(" Peak RSS (MiB)  = {:>30.2}\n", (self.peak_rss_kb as f64) / 1024.0);
+	    out += &format!(" Page faults     = {:>30}\n",
+	                    format!("{} minor, {} major", self.minor_faults, self.major_faults));
+	    out += &format!(" Ctx switches    = {:>30}\n",
+	                    format!("{} vol., {} invol.", self.voluntary_switches,
+	                            self.involuntary_switches));
+        return out;
+    }
+
+    // the hardware counter lines of the report, if counted ('mop' operations done)
+    pub fn render_counters( &self, mop: &f64 ) -> String
+    {
+        return self.counters.map_or(String::new(), |counters| counters.render(mop));
+    }
+
+    // as a JSON object
+    pub fn to_json( &self ) -> String
+    {
+        return format!("{{ \"peak_rss_kb\": {}, \"minor_faults\": {}, \"major_faults\": {}, \
+                        \"voluntary_switches\": {}, \"involuntary_switches\": {}, \"counters\": {} }}",
+                       self.peak_rss_kb, self.minor_faults, self.major_faults,
+                       self.voluntary_switches, self.involuntary_switches,
+                       self.counters.map_or(String::from("null"), |counters| counters.to_json()));
+    }
+}
@@ -0,0 +1,107 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                               (Common Code)                               //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    The outcome of a benchmark's verification, and the exit status every   //
+//    binary ends with, so job schedulers and CI can tell outcomes apart.    //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+// exit statuses
+pub const EXIT_SUCCESSFUL:    i32 = 0; // verification passed
+pub const EXIT_UNSUCCESSFUL:  i32 = 1; // verification failed
+pub const EXIT_NOT_PERFORMED: i32 = 2; // no reference values (e.g. unknown class)
+pub const EXIT_INVALID:       i32 = 3; // invalid arguments or configuration
+pub const EXIT_REGRESSION:    i32 = 4; // verified, but slower than the baseline
+
+
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Verification
+{
+    Successful,
+    Unsuccessful,
+    NotPerformed,
+}
+
+impl Verification
+{
+    // as print_results shows it
+    pub fn label( &self ) -> &'static str
+    {
+        return match self
+        {
+            Verification::Successful => "SUCCESSFUL",
+            Verification::Unsuccessful => "UNSUCCESSFUL",
+            Verification::NotPerformed => "NOT PERFORMED",
+        };
+    }
+
+    pub fn from_label( label: &str ) -> Option<Verification>
+    {
+        return [Verification::Successful, Verification::Unsuccessful, Verification::NotPerformed]
+                .into_iter().find(|v| v.label() == label);
+    }
+
+    pub fn exit_code( &self ) -> i32
+    {
+        return match self
+        {
+            Verification::Successful => EXIT_SUCCESSFUL,
+            Verification::Unsuccessful => EXIT_UNSUCCESSFUL,
+            Verification::NotPerformed => EXIT_NOT_PERFORMED,
+        };
+    }
+
+    /* the outcome of two verifications together: unsuccessful if either is,
+     * otherwise not performed if either was not
+     */
+    pub fn and( self, other: Verification ) -> Verification
+    {
+        return match (self, other)
+        {
+            (Verification::Unsuccessful, _) | (_, Verification::Unsuccessful) => Verification::Unsuccessful,
+            (Verification::NotPerformed, _) | (_, Verification::NotPerformed) => Verification::NotPerformed,
+            _ => Verification::Successful,
+        };
+    }
+
+    pub fn passed( &self ) -> bool
+    {
+        return *self == Verification::Successful;
+    }
+}
+
+impl From<bool> for Verification
+{
+    fn from( passed: bool ) -> Verification
+    {
+        return if passed { Verification::Successful } else { Verification::Unsuccessful };
+    }
+}
@@ -44,13 +44,12 @@ const CARGO: &str = env!("CARGO");
 mod print_results;
 pub use self::print_results::print_results as rs_print_results;
 
-pub mod f64rand;
 
 
-
-pub use std::time::{Duration, Instant};
+pub use std::time::Instant;
 
 // returns time since 'i' in microseconds
+#[allow(dead_code)]
 pub fn elapsed( i: &Instant ) -> u128
 {
     return i.elapsed().as_micros();
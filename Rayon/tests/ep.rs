@@ -0,0 +1,60 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                              (Rayon Version)                              //
+//                                                                           //
+//              Rust version of the APP Benchmark 1, the "EP",               //
+//                  or "embarassingly parallel" benchmark.                   //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    Checks of the EP binary: it must verify, and the sums it prints must   //
+//    match the reference ones of the official NPB for the class it was      //
+//    built for (CLASS), whatever the number of threads.                     //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+#[path = "../../Common/tests/binaries/mod.rs"]
+mod binaries;
+
+use binaries::check_ep;
+
+
+
+#[test]
+fn prints_the_reference_sums()
+{
+    check_ep(env!("CARGO_BIN_EXE_EP"), &["--threads", "2"]);
+}
+
+#[test]
+fn prints_the_same_sums_on_any_number_of_threads()
+{
+    let (sx_one, sy_one): (f64, f64) = check_ep(env!("CARGO_BIN_EXE_EP"), &["--threads", "1"]);
+    let (sx_many, sy_many): (f64, f64) = check_ep(env!("CARGO_BIN_EXE_EP"), &["--threads", "3"]);
+    assert!(((sx_one - sx_many) / sx_one).abs() <= 1.0e-12);
+    assert!(((sy_one - sy_many) / sy_one).abs() <= 1.0e-12);
+}
@@ -47,244 +47,129 @@
 
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
-#![allow(unused_assignments)]
+#![allow(clippy::needless_return)]
 
 use std::env;
+use std::fs;
+use std::process;
+
+use rusty_npb::affinity::Binding;
+use rusty_npb::args;
+use rusty_npb::backend::{self, BackendKind};
+use rusty_npb::baseline;
+use rusty_npb::ep::{self, EpResult};
+use rusty_npb::preflight::{self, Preflight};
+use rusty_npb::stats;
+use rusty_npb::trace;
+use rusty_npb::verification::{self, Verification};
 
 mod common;
 use common::*;
 
 
 
-fn verify( M: &usize, sx: &f64, sy: &f64 ) -> bool
-{
-    const EPSILON: f64  = 1.0e-8; // random numbers precision
-
-	let (sx_verify_value, sy_verify_value): (f64, f64);
-
-	if *M == 24
-	{
-		sx_verify_value = -3.247834652034740e+3;
-		sy_verify_value = -6.958407078382297e+3;
-	}
-	else if *M == 25
-	{
-		sx_verify_value = -2.863319731645753e+3;
-		sy_verify_value = -6.320053679109499e+3;
-	}
-	else if *M == 28
-	{
-		sx_verify_value = -4.295875165629892e+3;
-		sy_verify_value = -1.580732573678431e+4;
-	}
-	else if *M == 30
-	{
-		sx_verify_value =  4.033815542441498e+4;
-		sy_verify_value = -2.660669192809235e+4;
-	}
-	else if *M == 32
-	{
-		sx_verify_value =  4.764367927995374e+4;
-		sy_verify_value = -8.084072988043731e+4;
-	}
-	else if *M == 36
-	{
-		sx_verify_value =  1.982481200946593e+5;
-		sy_verify_value = -1.020596636361769e+5;
-	}
-	else if *M == 40
-	{
-		sx_verify_value = -5.319717441530e+05;
-		sy_verify_value = -3.688834557731e+05;
-	}
-	else
-	{
-		return false;
-	}
-
-	return (((sx - sx_verify_value).abs() / sx_verify_value) <= EPSILON) &&
-	       (((sy - sy_verify_value).abs() / sy_verify_value) <= EPSILON);
-}
-
-
-
 fn main()
 {
-    const M:       usize = // log2 of the number of complex pairs of (0, 1) unif. random numbers
-            if      CLASS == 'S' { 24 }
-            else if CLASS == 'W' { 25 }
-            else if CLASS == 'A' { 28 }
-            else if CLASS == 'B' { 30 }
-            else if CLASS == 'C' { 32 }
-            else if CLASS == 'D' { 36 }
-            else if CLASS == 'E' { 40 }
-            else                 { 00 };
-
-    const MK:      usize = 16; // log2 of the size of each batch of random numbers
-    const NK:      usize = 1 << MK;
-    const NQ:      usize = 10;
-    const A:       f64   = 1220703125.0;
-    const S:       f64   = 271828183.0;
-    const NK_PLUS: usize = (2 * NK) + 1; // 2NK + 1
-    const SIZE:    i128  = 1 << (M + 1); // total number of random nums. to be generated
-
-    /* number of "batches" of random number pairs generated.
-     * (adjust if the processors count isn't evenly divisible by the total number)
-     */
-    const TOTAL_BATCHES: usize = 1 << (M - MK); // 2^(M-MK)
-
-	/* ********************************************************************* */
-
     // processing command line arguments
     let args: Vec<String> = env::args().collect::<Vec<String>>()[1..].to_vec();
     let mut use_timers: bool = false;
-    for arg in args
+    for arg in args.iter()
     {
         if (arg == "--with-timers") || (arg == "-t") { use_timers = true; }
     }
+    let requested_threads: Option<usize> = args::or_exit(backend::requested_threads(&args));
+    let binding: Binding = args::or_exit(Binding::from_args(&args));
+    let repeat: usize = args::or_exit(args::number_option(&args, "--repeat", &1, &1));
+    let warmup: usize = args::or_exit(args::number_option(&args, "--warmup", &0, &0));
+    let json_path: Option<&str> = args::or_exit(args::option_value(&args, "--json"));
+    let trace_path: Option<&str> = args::or_exit(args::option_value(&args, "--trace"));
+    let baseline_path: Option<&str> = args::or_exit(args::option_value(&args, "--baseline"));
+    let threshold: f64 = args::or_exit(baseline::threshold(&args));
+    let preflight: Preflight = Preflight::from_args(&args);
+    let npb_compat: bool = args.iter().any(|arg| arg == "--npb-compat");
 
 	/* ********************************************************************* */
 
     // variables
-    let (mut x1, mut x2, mut t1, mut t2, mut t3, mut t4, an, mut Mops):
-            (f64, f64, f64, f64, f64, f64, f64, f64);
-	let (mut sx, mut sy, mut gc): (f64, f64, f64) = (0.0, 0.0, 0.0);
-    let (k_offset, mut kk, mut ik, /*mut*/ nit): (i64, i64, i64, i64);
-    let (mut dum0, mut dum1, mut dum2): (f64, f64, [f64; 1]) = (1.0, 1.0, [1.0; 1]);
-    let mut x: [f64; NK_PLUS] = [0.0; NK_PLUS];
-    let mut q: [f64; NQ] = [0.0; NQ];
-    #[allow(non_upper_case_globals)]
-    const available_threads: usize = 1;
-    let (mut time0, mut time1, mut time2): (u128, u128, u128) = (0, 0, 0);
+    let config: ep::Config = ep::Config
+    {
+        threads: requested_threads, binding, use_timers, warmup, repeat,
+        trace: trace_path.is_some(), perf: args.iter().any(|arg| arg == "--perf"),
+        ..ep::Config::new(&CLASS, &BackendKind::Serial)
+    };
+    let table: ep::EpClass = args::or_exit(ep::EpClass::of(&config));
+    let M: usize = table.M;
+    let available_threads: usize = config.backend.threads(&requested_threads);
+    let nit: i64 = 0; // ?
+
+    // nothing but the plan, if that is all that was asked for
+    if preflight.dry_run { preflight::print_plan(&table); return; }
 
 	/* ********************************************************************* */
 
     // beginning message
-	println!("\n\n {} {PACKAGE_VERSION}-serial - EP Benchmark\n",
-	        PACKAGE_NAME.to_string().replace("-", " ").replace("_", " "));
-	println!(" Number of random numbers generated: {}", SIZE);
-	println!(" Number of available threads:        1");
-
-    /* Call the random number generator functions and initialize
-     * the x-array to reduce the effects of paging on the timings.
-     * Also, call all mathematical functions that are used. Make
-     * sure these initializations cannot be eliminated as dead code.
-    */
-    f64rand::nrandlc(&0, &mut dum0, &dum1, &mut dum2);
-    dum2[0] = f64rand::randlc(&mut dum1, &dum0);
-    for i in 0usize..NK_PLUS { x[i] = -1.0e99; }
-    Mops = std::hint::black_box(f64::max(x[NK], dum2[0]).abs().sqrt().ln());
-
-    // starting main timer
-    let (start_moment, mut sub_start_moment) = (Instant::now(), Instant::now());
+	println!("\n\n {} {PACKAGE_VERSION}-{} - EP Benchmark\n",
+	        PACKAGE_NAME.to_string().replace("-", " ").replace("_", " "), config.backend.name());
+	println!(" Number of random numbers generated: {}", 1i128 << (M + 1));
+	println!(" Number of available threads:        {}", available_threads);
+    args::or_exit(preflight.check(&table));
 
 	/* ********************************************************************* */
 
-	t1 = A;
-	f64rand::nrandlc(&0, &mut t1, &A, &mut x);
-
-	// compute AN = A ^ (2 * NK) (mod 2^46)
-	t1 = A;
-	for _i in 0usize..(MK + 1)
-	{
-	    t2 = t1;
-	    f64rand::randlc(&mut t1, &t2);
-	}
-	an = t1;
+    let result: EpResult = args::or_exit(ep::run(&config));
+    let time0: f64 = result.time;
 
 	/* ********************************************************************* */
 
-    /* each instance of the following loop may be performed independently.
-     * we compute the k offsets separately to take into account the fact
-     * that some nodes have more numbers to generate than others
-     */
-    k_offset = -1;
-
-    for k in 1usize..=TOTAL_BATCHES
-    {
-        kk = k_offset + (k as i64);
-        t1 = S;
-        t2 = an;
-
-        if use_timers { sub_start_moment = Instant::now(); }
-
-        /* find starting seed t1 for this kk */
-        for _i in 1usize..=100usize
-        {
-	        ik = kk / 2;
-	        if (2 * ik) != kk { t3 = f64rand::randlc(&mut t1, &t2); }
-	        if ik == 0 { break; }
-	        t4 = t2;
-	        t3 = f64rand::randlc(&mut t2, &t4);
-	        kk = ik;
-        }
-
-        /* compute uniform pseudorandom numbers */
-        f64rand::nrandlc(&(2 * NK), &mut t1, &A, &mut x);
-        if use_timers { time2 += elapsed(&sub_start_moment); }
-
-        /* compute gaussian deviates by acceptance-rejection method and
-         * tally counts in concentric square annuli. this loop is not
-         * vectorizable.
-         */
-        if use_timers { sub_start_moment = Instant::now(); }
-        for i in 0usize..NK
-        {
-	        x1 = 2.0 * x[2 * i] - 1.0;
-	        x2 = 2.0 * x[(2 * i) + 1] - 1.0;
-	        t1 = (x1 * x1) + (x2 * x2);
-	        if t1 <= 1.0
-	        {
-		        t2 = (-2.0 * t1.ln() / t1).sqrt();
-		        t3 = x1 * t2; // Xi
-		        t4 = x2 * t2; // Yi
-		        q[f64::max(t3.abs(), t4.abs()) as usize] += 1.0; // count
-		        sx += t3; // sum of Xi
-		        sy += t4; // sum of Yi
-	        }
-        }
-        if use_timers { time1 += elapsed(&sub_start_moment); }
-    }
-
-	for i in 0usize..NQ { gc += q[i]; }
-
-	/* ********************************************************************* */
+    // EP-specific results summary
+	print!("{}", if npb_compat { result.summary_npb() } else { result.summary() });
 
-    // computing final stats
-    if use_timers
+    // repeated runs' statistics
+    if (repeat > 1) && !npb_compat
     {
-        time1 /= available_threads as u128;
-        time2 /= available_threads as u128;
+        stats::print_summary(&result.seconds, &result.mops, &warmup);
     }
-    time0 += elapsed(&start_moment);
-	nit = 0; // ?
-	let verified = verify(&M, &sx, &sy);
-	Mops = (f64::powf(2.0, (M + 1) as f64) / ((time0 as f64) / 1e6)) / 1e6;
-	        // (2^(M+1) / secs) / 1000000
-
-	/* ********************************************************************* */
-
-    // EP-specific results summary
-	println!("\n EP Benchmark Results:\n");
-	println!(" CPU Time           =                {:.4}", ((time0 as f64) / 1e6));
-	println!(" N                  =                2^{}", M);
-	println!(" No. Gaussian Pairs =                {:.0}", gc);
-	println!(" Sums               =                {:.15}", sx);
-	println!("                                     {:.15}", sy);
-	println!(" Counts:");
-	for i in 0usize..(NQ - 1) { println!("    {}    {:>15}", i, q[i] as i64); }
 
     // NPB benchmark results
-	rs_print_results("EP", &CLASS, &((M as i64) + 1), &0, &0, &nit, &((time0 as f64) / 1e6),
-                     &Mops, "Random numbers generated", &verified, &available_threads.to_string());
+	rs_print_results("EP", &CLASS, &((M as i64) + 1), &0, &0, &nit, &time0, &result.Mops,
+                     "Random numbers generated", &result.verification,
+                     &result.threads.to_string(), &requested_threads, &result.usage,
+                     &npb_compat);
+
+    // how the threads were bound (the backend's own choice, or --bind's)
+    if !npb_compat { println!(" Thread binding  = {:>30}\n", result.binding); }
 
     // timers' results
 	if use_timers
 	{
-		println!(" Total time:     {:.6} (100.00%)", ((time0 as f64) / 1e6));
+		println!(" Total time:     {:.6} (100.00%)", time0);
 		println!(" Gaussian pairs: {:.6} ({:>6.2}%)",
-		        ((time1 as f64) / 1e6), ((time1 as f64) / (time0 as f64)) * 100.0);
+		        result.time_gaussian, (result.time_gaussian / time0) * 100.0);
 		println!(" Random numbers: {:.6} ({:>6.2}%)",
-		        ((time2 as f64) / 1e6), ((time2 as f64) / (time0 as f64)) * 100.0);
+		        result.time_random, (result.time_random / time0) * 100.0);
 	}
+
+    // machine-readable results, and their comparison with a previous run's
+    let json: String = stats::to_json("EP", &CLASS, result.backend, &result.threads, &warmup,
+                                      &result.verification, &result.seconds, &result.mops,
+                                      &result.usage);
+    if let Some(path) = json_path
+    {
+        args::or_exit(fs::write(path, &json).map_err(|e| format!("{}: {}", path, e)));
+    }
+    if let Some(path) = trace_path
+    {
+        args::or_exit(fs::write(path, trace::to_json("EP", &result.trace))
+                              .map_err(|e| format!("{}: {}", path, e)));
+    }
+    // (only a verified run is worth comparing: a wrong one fails on its own)
+    if let Some(path) = baseline_path.filter(|_| result.verification == Verification::Successful)
+    {
+        if args::or_exit(baseline::compare(&json, path, &threshold))
+        {
+            process::exit(verification::EXIT_REGRESSION);
+        }
+    }
+
+    process::exit(result.verification.exit_code());
 }
@@ -0,0 +1,133 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                             (Serial Version)                              //
+//                                                                           //
+//               Rust version of the pseudo-application "SP",                //
+//               or "scalar penta-diagonal" solver benchmark.                //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//   The original NPB 3.4.1 version was written in Fortran and belongs to:   //
+//                   http://www.nas.nasa.gov/Software/NPB/                   //
+//                     Authors of the Fortran code are:                      //
+//                  R. Van der Wijngaart, W. Saphir, H. Jin                  //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    This benchmark solves three sets of uncoupled systems of equations,    //
+//    resulting from an approximate factorization of the Navier-Stokes       //
+//    equations (Beam-Warming), first in the x, then in the y and finally    //
+//    in the z direction. After diagonalization, each of these systems is    //
+//    scalar and penta-diagonal.                                             //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+#![allow(non_snake_case)]
+#![allow(clippy::needless_return)]
+
+use std::env;
+use std::process;
+
+use rusty_npb::args;
+use rusty_npb::backend::BackendKind;
+use rusty_npb::preflight::{self, Preflight};
+use rusty_npb::sp::{self, SpResult};
+
+mod common;
+use common::*;
+
+
+
+fn main()
+{
+    // processing command line arguments
+    let args: Vec<String> = env::args().collect::<Vec<String>>()[1..].to_vec();
+    let mut use_timers: bool = false;
+    for arg in args.iter()
+    {
+        if (arg == "--with-timers") || (arg == "-t") { use_timers = true; }
+    }
+    let preflight: Preflight = Preflight::from_args(&args);
+    let npb_compat: bool = args.iter().any(|arg| arg == "--npb-compat");
+
+	/* ********************************************************************* */
+
+    // variables
+    let config: sp::Config = sp::Config
+    {
+        use_timers, progress: true, perf: args.iter().any(|arg| arg == "--perf"),
+        ..sp::Config::new(&CLASS, &BackendKind::Serial)
+    };
+    let table: sp::SpClass = args::or_exit(sp::SpClass::of(&config));
+    let grid_points: [usize; 3] = table.grid_points;
+
+    // nothing but the plan, if that is all that was asked for
+    if preflight.dry_run { preflight::print_plan(&table); return; }
+
+	/* ********************************************************************* */
+
+    // beginning message
+	println!("\n\n {} {PACKAGE_VERSION}-serial - SP Benchmark\n",
+	        PACKAGE_NAME.to_string().replace("-", " ").replace("_", " "));
+	println!(" Size: {:>4}x{:>4}x{:>4}  (class {})",
+	        grid_points[0], grid_points[1], grid_points[2], CLASS);
+	println!(" Iterations: {:>4}    dt: {:>10.6}", table.niter, table.dt);
+	println!(" Number of available threads:        1");
+    args::or_exit(preflight.check(&table));
+    println!();
+
+	/* ********************************************************************* */
+
+    let result: SpResult = args::or_exit(sp::run(&config));
+
+	/* ********************************************************************* */
+
+    // verification
+	print!("{}", result.comparison());
+
+    // NPB benchmark results
+	rs_print_results("SP", &CLASS, &(grid_points[0] as i64), &(grid_points[1] as i64),
+	                 &(grid_points[2] as i64), &(table.niter as i64), &result.time, &result.Mops,
+	                 "floating point", &result.verification, &result.threads.to_string(), &None,
+	                 &result.usage, &npb_compat);
+
+    // timers' results
+	if use_timers
+	{
+	    let sections: [(&str, usize); 6] = [("RHS:", sp::T_RHS), ("TXINVR:", sp::T_TXINVR),
+	                                        ("X solve:", sp::T_XSOLVE), ("Y solve:", sp::T_YSOLVE),
+	                                        ("Z solve:", sp::T_ZSOLVE), ("Add:", sp::T_ADD)];
+		println!(" Total time:     {:.6} (100.00%)", result.time);
+		for (name, timer) in sections
+		{
+		    let t: f64 = result.timers[timer];
+		    println!(" {:<15} {:.6} ({:>6.2}%)", name, t, (t / result.time) * 100.0);
+		}
+	}
+
+    process::exit(result.verification.exit_code());
+}
@@ -0,0 +1,75 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                             (Serial Version)                              //
+//                                                                           //
+//                     Checks of the "SP", "LU" and "DT"                     //
+//                                benchmarks.                                //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    Checks that every kernel besides EP (tests/ep.rs) verifies against     //
+//    the reference values of the official NPB for the class it was built    //
+//    for (CLASS).                                                           //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+#[path = "../../Common/tests/binaries/mod.rs"]
+mod binaries;
+
+use binaries::check_verifies;
+
+
+
+#[test]
+fn sp_verifies()
+{
+    check_verifies(env!("CARGO_BIN_EXE_SP"), &[]);
+}
+
+#[test]
+fn lu_verifies()
+{
+    check_verifies(env!("CARGO_BIN_EXE_LU"), &[]);
+}
+
+#[test]
+fn dt_black_hole_verifies()
+{
+    check_verifies(env!("CARGO_BIN_EXE_DT"), &["BH"]);
+}
+
+#[test]
+fn dt_white_hole_verifies()
+{
+    check_verifies(env!("CARGO_BIN_EXE_DT"), &["WH"]);
+}
+
+#[test]
+fn dt_shuffle_verifies()
+{
+    check_verifies(env!("CARGO_BIN_EXE_DT"), &["SH"]);
+}
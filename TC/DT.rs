@@ -0,0 +1,140 @@
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//                                 Rusty NPB                                 //
+//                     (Threads & Channels Version - TC)                     //
+//                                                                           //
+//                    Rust version of the benchmark "DT",                    //
+//                       or "Data Traffic" benchmark.                        //
+//                                                                           //
+//                                MIT License                                //
+//                Copyright (C) 2023 Pedro Vernetti Gonçalves                //
+//                                                                           //
+//  Permission is hereby granted, free of charge, to any person obtaining a  //
+// copy of this software and associated documentation files (the "Software"),//
+// to deal in the Software without restriction, including without limitation //
+//  the rights to use, copy, modify, merge, publish, distribute, sublicense, //
+//   and/or sell copies of the Software, and to permit persons to whom the   //
+//    Software is furnished to do so, subject to the following conditions:   //
+//  The above copyright notice and this permission notice shall be included  //
+//          in all copies or substantial portions of the Software.           //
+//  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS  //
+//        OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF         //
+//   MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  //
+//    IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY   //
+// CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT //
+// OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR  //
+//                 THE USE OR OTHER DEALINGS IN THE SOFTWARE.                //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//      The original NPB 3.4.1 version was written in C and belongs to:      //
+//                   http://www.nas.nasa.gov/Software/NPB/                   //
+//                         Authors of the C code is:                         //
+//                                M. Frumkin                                 //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+//    This benchmark passes feature arrays through a communication graph:    //
+//    sources generate them, comparators filter and combine the arrays they  //
+//    receive, and sinks reduce them to a checksum. The graph is one of the  //
+//    "black hole" (BH: many sources, one sink), "white hole" (WH: one       //
+//    source, many sinks) or "shuffle" (SH: a butterfly network) ones.       //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+
+
+#![allow(non_snake_case)]
+#![allow(clippy::needless_return)]
+
+use std::env;
+use std::process;
+
+use rusty_npb::affinity::Binding;
+use rusty_npb::args;
+use rusty_npb::backend::{self, BackendKind};
+use rusty_npb::dt::{self, DtResult};
+use rusty_npb::preflight::{self, Preflight};
+use rusty_npb::verification;
+
+mod common;
+use common::*;
+
+
+
+fn main()
+{
+    // processing command line arguments
+    let args: Vec<String> = env::args().collect::<Vec<String>>()[1..].to_vec();
+    let mut use_timers: bool = false;
+    let mut graph: Option<&str> = None;
+    for arg in args.iter()
+    {
+        if (arg == "--with-timers") || (arg == "-t") { use_timers = true; }
+        else if (arg == "BH") || (arg == "WH") || (arg == "SH") { graph = Some(arg); }
+    }
+    let requested_threads: Option<usize> = args::or_exit(backend::requested_threads(&args));
+    let binding: Binding = args::or_exit(Binding::from_args(&args));
+    let preflight: Preflight = Preflight::from_args(&args);
+    let npb_compat: bool = args.iter().any(|arg| arg == "--npb-compat");
+
+    let graph: &str = match graph
+    {
+        Some(graph) => graph,
+        None =>
+        {
+            println!(" ** Usage: DT [--with-timers] [--threads N] [--bind POLICY] [--force]");
+            println!("              [--dry-run] [--perf] [--npb-compat]");
+            println!("              GraphName");
+            println!(" ** Where GraphName is one of BH, WH or SH.");
+            process::exit(verification::EXIT_INVALID);
+        },
+    };
+
+	/* ********************************************************************* */
+
+    // variables
+    let config: dt::Config = dt::Config
+    {
+        threads: requested_threads, binding, perf: args.iter().any(|arg| arg == "--perf"),
+        ..dt::Config::new(&CLASS, graph, &BackendKind::ThreadsChannels)
+    };
+    let table: dt::DtClass = args::or_exit(dt::DtClass::of(&config));
+
+    // nothing but the plan, if that is all that was asked for
+    if preflight.dry_run { preflight::print_plan(&table); return; }
+
+	/* ********************************************************************* */
+
+    // beginning message
+	println!("\n\n {} {PACKAGE_VERSION}-TC - DT Benchmark\n",
+	        PACKAGE_NAME.to_string().replace("-", " ").replace("_", " "));
+	args::or_exit(dt::Graph::of(&config)).show();
+	println!(" Number of available threads:        {}", table.threads);
+    args::or_exit(preflight.check(&table));
+    println!();
+
+	/* ********************************************************************* */
+
+    let result: DtResult = args::or_exit(dt::run(&config));
+
+	/* ********************************************************************* */
+
+    // verification
+	print!("{}", result.comparison());
+
+    // NPB benchmark results
+	rs_print_results("DT", &CLASS, &(result.featnum as i64), &0, &0, &(result.nodes as i64),
+	                 &result.time, &result.Mops, "bytes transmitted", &result.verification,
+	                 &result.threads.to_string(), &requested_threads, &result.usage, &npb_compat);
+
+    // timers' results
+	if use_timers
+	{
+		println!(" Total time:     {:.6} (100.00%)", result.time);
+	}
+
+    process::exit(result.verification.exit_code());
+}
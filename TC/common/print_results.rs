@@ -31,57 +31,66 @@
 //                                                                           //
 ///////////////////////////////////////////////////////////////////////////////
 
+
+
+use rusty_npb::backend;
+use rusty_npb::host::HostInfo;
+use rusty_npb::report::{BenchmarkReport, BuildInfo};
+use rusty_npb::usage::ResourceUsage;
+use rusty_npb::verification::Verification;
+
+
+
+// the build of this package, as recorded by build.rs
+fn build_info() -> BuildInfo
+{
+    let dependencies: Vec<&'static str> =
+            env!("NPB_DEPENDENCIES").split(", ").filter(|d| !d.is_empty()).collect();
+
+	return BuildInfo
+	{
+	    package: env!("CARGO_PKG_NAME"),
+	    authors: env!("CARGO_PKG_AUTHORS"),
+	    version: env!("CARGO_PKG_VERSION"),
+	    compile_date: pkg_compile_time::pkg_compile_date!(),
+	    rustc_version: env!("NPB_RUSTC_VERSION"),
+	    rustc: env!("NPB_RUSTC"),
+	    rustflags: env!("NPB_RUSTFLAGS"),
+	    profile: env!("NPB_PROFILE"),
+	    target: env!("NPB_TARGET"),
+	    target_cpu: env!("NPB_TARGET_CPU"),
+	    target_features: env!("NPB_TARGET_FEATURES"),
+	    rayon_version: None,
+	    dependencies,
+	};
+}
+
+// takes the same arguments as print_results.f
+#[allow(clippy::too_many_arguments)]
 pub fn print_results( name: &str, class_npb: &char, n1: &i64, n2: &i64, n3: &i64,
 	    	          niter: &i64, t: &f64, mops: &f64, optype: &str,
-	    	          passed_verification: &bool, totalthreads: &str )
+	    	          verification: &Verification, totalthreads: &str,
+	    	          requestedthreads: &Option<usize>, usage: &ResourceUsage,
+	    	          npb_compat: &bool )
 {
-	let size =
-	    if (name == "IS") && (*n3 == 0) {
-	        (n1 * (if *n2 != 0 { n2 } else { &1 })).to_string() }
-	    else if (*n2 == 0) && (*n3 == 0) {
-	        if name == "EP" { (1i128 << n1).to_string() }
-	        else { n1.to_string() } }
-	    else {
-	        format!("{:>4}x{:>4}x{:>4}", n1, n2, n3) };
-	let verification_status: String =
-	        /*if passed_verification < &0 { String::from("NOT PERFORMED") }
-	        else if passed_verification > &0 { String::from("SUCCESSFUL") }
-	        else { String::from("UNSUCCESSFUL") }; */
-	        if *passed_verification { String::from("SUCCESSFUL") }
-	        else { String::from("UNSUCCESSFUL") };
-    let rustc: &str =
-            match option_env!("RUSTC") { Some(v) => v, None => "rustc", };
-    let rustc_version: String = rustc_version::version().unwrap().to_string();
-    let authors: String =
-            env!("CARGO_PKG_AUTHORS").to_string()
-                    .replace(":", "\n                            ");
-    let rustflags: &str =
-            match option_env!("RUSTFLAGS")
-            {
-                Some(v) => v,
-                None => "-C opt-level=3 -C debuginfo=0 -C lto=true",
-            };
-
-	println!("\n\n {name} Benchmark Completed\n");
-	println!(" class_npb       = {:>30}", class_npb);
-    println!(" Size            = {:>30}", size);
-	println!(" Total threads   = {:>30}", totalthreads);
-	println!(" Iterations      = {:>30}", niter);
-	println!(" Time in seconds = {:>30}", t);
-	println!(" Mop/s total     = {:>30}", mops);
-	println!(" Operation type  = {:>30}", optype);
-	println!(" Verification    = {:>30}", verification_status);
-	println!(" Version         = {:>30}", env!("CARGO_PKG_VERSION"));
-	println!(" Compile date    = {:>30}", pkg_compile_time::pkg_compile_date!());
-	println!(" Compiler ver    = {:>30}", rustc_version);
-	println!("\n Compile options:");
-	println!("    RUSTC        = {:>30}", rustc);
-	println!("    RUSTFLAGS    = {:>30}", rustflags);
-	println!("    RAND         = {:>30}", "f64rand");
+	let report: BenchmarkReport = BenchmarkReport
+	{
+	    name: name.to_string(),
+	    class: *class_npb,
+	    size: BenchmarkReport::size(name, n1, n2, n3),
+	    total_threads: totalthreads.to_string(),
+	    requested_threads: *requestedthreads,
+	    available_threads: Some(requestedthreads.unwrap_or_else(backend::available_threads)),
+	    iterations: *niter,
+	    time: *t,
+	    mops: *mops,
+	    optype: optype.to_string(),
+	    verification: *verification,
+	    usage: *usage,
+	    build: build_info(),
+	    host: HostInfo::collect(),
+	};
 
-	println!("\n {:-<70}", "");
-	println!(" {} is developed by: {authors}",
-	        env!("CARGO_PKG_NAME").to_string().replace("-", " ").replace("_", " "));
-	println!(" In case of questions or problems, please send an e-mail.");
-	println!(" {:-<70}\n", "");
+	// the reference NPB's block instead, for the scripts that parse its logs
+	if *npb_compat { print!("{}", report.render_npb()); } else { report.print(); }
 }